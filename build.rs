@@ -2,6 +2,10 @@ use std::{env,path::PathBuf, process::Command};
 
 
 fn main() {
+    // 只有 GTK 程序需要编译界面和资源文件
+    if env::var_os("CARGO_FEATURE_GUI").is_none() {
+        return;
+    }
     let root_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
     let build_script = root_dir.join("build.sh");
     let output = Command::new("bash")
@@ -0,0 +1,306 @@
+//! 命令行模式，不需要 GTK，方便在编辑器、git hooks 和 Makefile 中使用
+//!
+//! ```text
+//! asciibox flowchart [--format ascii|svgbob|svg|dot] [--from asciibox|dot] [--border single] [FILE]
+//! asciibox table [--mode markdown|gfm|asciidoc|csv|tsv|html|rst-grid|rst-simple|org|latex|mediawiki|jira|box[-double|-rounded|-heavy]] [--cell-width 40] [--line-width 99] [FILE]
+//! asciibox svgbob [--format svg|ascii|unicode] [FILE]
+//! ```
+
+use std::io::{self, Read};
+
+use crate::core::{
+    AsciiBoxMap, BoxBorder, TableFormator, TableMode, svgbob_to_unicode, unicode_to_svgbob,
+};
+
+pub const USAGE: &str = "\
+Usage: asciibox <COMMAND> [OPTIONS] [FILE]
+
+Reads FILE (or stdin when FILE is missing or `-`) and writes the result to stdout.
+
+Commands:
+  flowchart  Render an asciibox flowchart
+  table      Format a table
+  svgbob     Convert ascii or unicode art
+  help       Show this message
+
+Flowchart options:
+  --format <ascii|svgbob|svg|dot>    Output format [default: ascii]
+  --from <asciibox|dot>              Input format [default: asciibox]
+  --border <single|double|rounded|heavy|ascii>
+                                     Node border style [default: single]
+
+Table options:
+  --mode <markdown|gfm|asciidoc|csv|tsv|html|rst-grid|rst-simple|org|latex|mediawiki|jira|
+         box|box-double|box-rounded|box-heavy|box-ascii>
+                                     Table format [default: markdown]
+  --cell-width <N>                   Max cell width [default: 40]
+  --line-width <N>                   Max line width [default: 99]
+
+Svgbob options:
+  --format <svg|ascii|unicode>       Output format [default: svg]
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Flowchart,
+    Table,
+    Svgbob,
+    Help,
+}
+
+impl Command {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "flowchart" => Some(Command::Flowchart),
+            "table" => Some(Command::Table),
+            "svgbob" => Some(Command::Svgbob),
+            "help" => Some(Command::Help),
+            _ => None,
+        }
+    }
+}
+
+/// 解析后的命令行参数
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub command: Command,
+    /// 输入文件，None 表示从 stdin 读取
+    pub input: Option<String>,
+    /// 输出格式，None 表示使用命令的默认格式
+    pub format: Option<String>,
+    pub from: Option<String>,
+    pub mode: TableMode,
+    pub cell_width: usize,
+    pub line_width: usize,
+    pub border: BoxBorder,
+}
+
+impl Options {
+    pub fn new(command: Command) -> Self {
+        Self {
+            command,
+            input: None,
+            format: None,
+            from: None,
+            mode: TableMode::Markdown,
+            cell_width: 40,
+            line_width: 99,
+            border: BoxBorder::Single,
+        }
+    }
+
+    /// 解析参数，不包含程序名
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let Some(first) = args.first() else {
+            return Err("missing command".to_string());
+        };
+        let command =
+            Command::from_name(first).ok_or_else(|| format!("unknown command: {}", first))?;
+        let mut opts = Options::new(command);
+
+        let mut iter = args[1..].iter();
+        while let Some(arg) = iter.next() {
+            if arg == "-h" || arg == "--help" {
+                opts.command = Command::Help;
+                continue;
+            }
+            if arg == "-" || !arg.starts_with("--") {
+                if opts.input.is_some() {
+                    return Err(format!("unexpected argument: {}", arg));
+                }
+                opts.input = (arg != "-").then(|| arg.clone());
+                continue;
+            }
+            // 支持 --flag value 和 --flag=value 两种写法
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, value.to_string()),
+                None => {
+                    let value = iter
+                        .next()
+                        .ok_or_else(|| format!("missing value for {}", arg))?;
+                    (arg.as_str(), value.clone())
+                }
+            };
+            match flag {
+                "--format" => opts.format = Some(value.to_lowercase()),
+                "--from" => opts.from = Some(value.to_lowercase()),
+                "--mode" => {
+                    opts.mode = TableMode::from_name(&value)
+                        .ok_or_else(|| format!("unknown table mode: {}", value))?;
+                }
+                "--cell-width" => opts.cell_width = parse_width(flag, &value)?,
+                "--line-width" => opts.line_width = parse_width(flag, &value)?,
+                "--border" => {
+                    opts.border = BoxBorder::from_name(&value)
+                        .ok_or_else(|| format!("unknown border style: {}", value))?;
+                }
+                _ => return Err(format!("unknown option: {}", flag)),
+            }
+        }
+        Ok(opts)
+    }
+}
+
+fn parse_width(flag: &str, value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(v) if v > 0 => Ok(v),
+        _ => Err(format!("invalid value for {}: {}", flag, value)),
+    }
+}
+
+/// 第一个参数是子命令时以命令行方式运行
+pub fn is_command(args: &[String]) -> bool {
+    args.first()
+        .is_some_and(|a| Command::from_name(a).is_some())
+}
+
+/// 对输入执行命令并返回输出
+pub fn execute(opts: &Options, input: &str) -> Result<String, String> {
+    match opts.command {
+        Command::Help => Ok(USAGE.to_string()),
+        Command::Flowchart => flowchart(opts, input),
+        Command::Table => TableFormator::new(opts.cell_width, opts.line_width)
+            .format(input, &opts.mode)
+            .ok_or_else(|| "input is not a table".to_string()),
+        Command::Svgbob => match opts.format.as_deref().unwrap_or("svg") {
+            "svg" => Ok(svgbob::to_svg_string_pretty(&unicode_to_svgbob(input))),
+            "ascii" => Ok(unicode_to_svgbob(input)),
+            "unicode" => Ok(svgbob_to_unicode(input)),
+            other => Err(format!("unknown svgbob format: {}", other)),
+        },
+    }
+}
+
+fn flowchart(opts: &Options, input: &str) -> Result<String, String> {
+    let map = match opts.from.as_deref().unwrap_or("asciibox") {
+        "asciibox" => AsciiBoxMap::load_content(input),
+        "dot" => AsciiBoxMap::load_dot(input)?,
+        other => return Err(format!("unknown input format: {}", other)),
+    };
+    let format = opts.format.as_deref().unwrap_or("ascii");
+    match format {
+        "dot" => return Ok(map.to_dot()),
+        "svgbob" => return Ok(map.render_to_svgbob()),
+        "ascii" | "svg" => {}
+        other => return Err(format!("unknown flowchart format: {}", other)),
+    }
+
+    let mut graph = map.to_rendered();
+    graph.set_border_style(opts.border);
+    graph.run_simulation();
+    if format == "svg" {
+        return Ok(graph.render_to_svg());
+    }
+    let mut output = String::new();
+    for line in graph.render_to_grid().to_string().lines() {
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    Ok(output.trim_matches('\n').to_string() + "\n")
+}
+
+/// 运行命令行，返回进程退出码
+pub fn run(args: &[String]) -> u8 {
+    let opts = match Options::parse(args) {
+        Ok(opts) => opts,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    let input = if opts.command == Command::Help {
+        String::new()
+    } else {
+        match read_input(opts.input.as_deref()) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return 1;
+            }
+        }
+    };
+
+    match execute(&opts, &input) {
+        Ok(output) => {
+            print!("{}", output);
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+fn read_input(path: Option<&str>) -> io::Result<String> {
+    match path {
+        Some(path) => std::fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let opts = Options::parse(&args(
+            "table --mode=gfm --cell-width 10 --line-width=50 a.txt",
+        ))
+        .unwrap();
+        assert_eq!(opts.command, Command::Table);
+        assert_eq!(opts.mode, TableMode::MarkdownGFM);
+        assert_eq!((opts.cell_width, opts.line_width), (10, 50));
+        assert_eq!(opts.input.as_deref(), Some("a.txt"));
+
+        let opts = Options::parse(&args("flowchart --border rounded -")).unwrap();
+        assert_eq!(opts.border, BoxBorder::Rounded);
+        assert_eq!(opts.input, None);
+
+        let opts = Options::parse(&args("table --mode html")).unwrap();
+        assert_eq!(opts.mode, TableMode::Html);
+        assert!(Options::parse(&args("table --mode rtf")).is_err());
+        let opts = Options::parse(&args("table --mode box-heavy")).unwrap();
+        assert_eq!(opts.mode, TableMode::BoxDrawing(BoxBorder::Heavy));
+        assert!(Options::parse(&args("table --cell-width")).is_err());
+        assert!(Options::parse(&args("table --cell-width 0")).is_err());
+        assert!(Options::parse(&args("paint")).is_err());
+        assert!(is_command(&args("svgbob x")));
+        assert!(!is_command(&args("--gapplication-service")));
+    }
+
+    #[test]
+    fn test_execute() {
+        let opts = Options::parse(&args("flowchart --border ascii")).unwrap();
+        let output = execute(&opts, "a[Start]-->b[End]").unwrap();
+        assert!(output.contains("| Start |"), "{}", output);
+        assert!(output.lines().all(|l| l == l.trim_end()));
+
+        let opts = Options::parse(&args("flowchart --format dot")).unwrap();
+        let output = execute(&opts, "a-->b").unwrap();
+        assert!(output.contains("\"a\" -> \"b\";"), "{}", output);
+
+        let opts = Options::parse(&args("flowchart --from dot --format svg")).unwrap();
+        let output = execute(&opts, "digraph { a -> b }").unwrap();
+        assert!(output.starts_with("<svg"), "{}", output);
+
+        let opts = Options::parse(&args("table --mode asciidoc")).unwrap();
+        let output = execute(&opts, "a,b\n1,2").unwrap();
+        assert!(output.contains("|==="), "{}", output);
+
+        let opts = Options::parse(&args("svgbob --format ascii")).unwrap();
+        assert_eq!(execute(&opts, "┌─┐").unwrap(), "+-+");
+        let opts = Options::parse(&args("svgbob")).unwrap();
+        assert!(execute(&opts, "+-+").unwrap().contains("<svg"));
+    }
+}
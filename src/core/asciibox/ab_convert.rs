@@ -0,0 +1,224 @@
+//! Conversion between Unicode box-drawing glyphs and svgbob ASCII.
+//!
+//! svgbob only understands ASCII shapes (`+-|.'` and `<>^v` arrows), so
+//! pictures drawn with [`BoxBorder::Single`](super::ab_graph::BoxBorder),
+//! `Double` or `Rounded` have to be translated before they can be turned into
+//! SVG. The reverse direction looks at the neighbours of every `+ . '` to
+//! pick the right corner or junction glyph again.
+//!
+//! Sharp corners, rounded corners, tees, crosses, arrows and double
+//! horizontal lines survive a round trip. svgbob has no double vertical line,
+//! so `║` comes back as `│` and the corners touching it lose their double
+//! vertical stroke.
+
+/// 将 unicode 制表符转换为 svgbob 可识别的 ascii 字符
+pub fn unicode_to_svgbob(text: &str) -> String {
+    text.chars().map(unicode_to_ascii).collect()
+}
+
+fn unicode_to_ascii(c: char) -> char {
+    match c {
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' => '-',
+        '═' => '=',
+        '│' | '┃' | '║' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' => '|',
+        '╭' | '╮' => '.',
+        '╰' | '╯' => '\'',
+        '↓' | '▼' | '▽' => 'v',
+        '↑' | '▲' | '△' => '^',
+        '→' | '▶' | '▷' => '>',
+        '←' | '◀' | '◁' => '<',
+        '╱' => '/',
+        '╲' => '\\',
+        '╳' => 'X',
+        // 所有直角、T 形以及十字交叉
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' => '+',
+        '┏' | '┓' | '┗' | '┛' | '┣' | '┫' | '┳' | '┻' | '╋' => '+',
+        '╔' | '╗' | '╚' | '╝' | '╠' | '╣' | '╦' | '╩' | '╬' => '+',
+        '╒' | '╕' | '╘' | '╛' | '╞' | '╡' | '╤' | '╧' | '╪' => '+',
+        '╓' | '╖' | '╙' | '╜' | '╟' | '╢' | '╥' | '╨' | '╫' => '+',
+        _ => c,
+    }
+}
+
+/// 将 svgbob 的 ascii 图形转换回 unicode 制表符
+pub fn svgbob_to_unicode(text: &str) -> String {
+    let lines: Vec<Vec<char>> = text.split('\n').map(|l| l.chars().collect()).collect();
+    let at = |x: isize, y: isize| -> char {
+        if x < 0 || y < 0 {
+            return ' ';
+        }
+        lines
+            .get(y as usize)
+            .and_then(|l| l.get(x as usize))
+            .copied()
+            .unwrap_or(' ')
+    };
+
+    let mut out: Vec<String> = Vec::with_capacity(lines.len());
+    for (y, line) in lines.iter().enumerate() {
+        let mut s = String::with_capacity(line.len());
+        for (x, &c) in line.iter().enumerate() {
+            let (x, y) = (x as isize, y as isize);
+            let links = Links {
+                up: connects_down(at(x, y - 1)),
+                down: connects_up(at(x, y + 1)),
+                left: connects_right(at(x - 1, y)),
+                right: connects_left(at(x + 1, y)),
+                double: at(x - 1, y) == '=' || at(x + 1, y) == '=',
+            };
+            s.push(ascii_to_unicode(
+                c,
+                &links,
+                at(x, y - 1),
+                at(x, y + 1),
+                at(x - 1, y),
+                at(x + 1, y),
+            ));
+        }
+        out.push(s);
+    }
+    out.join("\n")
+}
+
+/// 当前字符和四周的连接关系
+struct Links {
+    up: bool,
+    down: bool,
+    left: bool,
+    right: bool,
+    // 水平方向是否是双线
+    double: bool,
+}
+
+fn connects_down(c: char) -> bool {
+    matches!(c, '|' | '+' | '.' | '^')
+}
+
+fn connects_up(c: char) -> bool {
+    matches!(c, '|' | '+' | '\'' | 'v')
+}
+
+fn connects_right(c: char) -> bool {
+    matches!(c, '-' | '=' | '+' | '.' | '\'' | '<')
+}
+
+fn connects_left(c: char) -> bool {
+    matches!(c, '-' | '=' | '+' | '.' | '\'' | '>')
+}
+
+fn ascii_to_unicode(c: char, l: &Links, up: char, down: char, left: char, right: char) -> char {
+    match c {
+        '-' if l.left || l.right => '─',
+        '=' if l.left || l.right => '═',
+        '|' if l.up || l.down => '│',
+        '+' => junction(l).unwrap_or('+'),
+        '.' if l.down && l.right && !l.left => '╭',
+        '.' if l.down && l.left && !l.right => '╮',
+        '\'' if l.up && l.right && !l.left => '╰',
+        '\'' if l.up && l.left && !l.right => '╯',
+        'v' if l.up || matches!(down, '-' | '=' | '+' | '.') => '↓',
+        '^' if l.down || matches!(up, '-' | '=' | '+' | '\'') => '↑',
+        '>' if l.left || matches!(right, '|' | '+') => '→',
+        '<' if l.right || matches!(left, '|' | '+') => '←',
+        _ => c,
+    }
+}
+
+/// 根据四周连接选择转角或者交叉字符
+fn junction(l: &Links) -> Option<char> {
+    let c = match (l.up, l.down, l.left, l.right, l.double) {
+        (false, true, false, true, false) => '┌',
+        (false, true, true, false, false) => '┐',
+        (true, false, false, true, false) => '└',
+        (true, false, true, false, false) => '┘',
+        (true, true, false, true, false) => '├',
+        (true, true, true, false, false) => '┤',
+        (false, true, true, true, false) => '┬',
+        (true, false, true, true, false) => '┴',
+        (true, true, true, true, false) => '┼',
+        (false, true, false, true, true) => '╒',
+        (false, true, true, false, true) => '╕',
+        (true, false, false, true, true) => '╘',
+        (true, false, true, false, true) => '╛',
+        (true, true, false, true, true) => '╞',
+        (true, true, true, false, true) => '╡',
+        (false, true, true, true, true) => '╤',
+        (true, false, true, true, true) => '╧',
+        (true, true, true, true, true) => '╪',
+        (false, false, true, _, false) | (false, false, _, true, false) => '─',
+        (false, false, true, _, true) | (false, false, _, true, true) => '═',
+        (true, _, false, false, _) | (_, true, false, false, _) => '│',
+        _ => return None,
+    };
+    Some(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::asciibox::ab_graph::{BoxBorder, RenderedGraph};
+    use petgraph::graph::DiGraph;
+
+    #[test]
+    fn test_box_round_trip() {
+        let boxes = "┌──┬──┐\n│a │b │\n├──┼──┤\n└──┴──┘\n╭──╮\n│c │\n╰──╯";
+        let ascii = unicode_to_svgbob(boxes);
+        assert_eq!(
+            ascii,
+            "+--+--+\n|a |b |\n+--+--+\n+--+--+\n.--.\n|c |\n'--'"
+        );
+        assert_eq!(svgbob_to_unicode(&ascii), boxes);
+    }
+
+    #[test]
+    fn test_double_horizontal() {
+        let boxes = "╒══╤══╕\n│  │  │\n╞══╪══╡\n╘══╧══╛";
+        let ascii = unicode_to_svgbob(boxes);
+        assert_eq!(ascii, "+==+==+\n|  |  |\n+==+==+\n+==+==+");
+        assert_eq!(svgbob_to_unicode(&ascii), boxes);
+    }
+
+    #[test]
+    fn test_text_untouched() {
+        let text = "a-b | c + d. it's v2 > 1";
+        assert_eq!(svgbob_to_unicode(text), text);
+        assert_eq!(unicode_to_svgbob(text), text);
+    }
+
+    #[test]
+    fn test_rendered_graph_round_trip() {
+        for border in [BoxBorder::Single, BoxBorder::Rounded] {
+            let mut graph: DiGraph<&str, &str> = DiGraph::new();
+            let a = graph.add_node("Start");
+            let b = graph.add_node("End");
+            graph.add_edge(a, b, "go");
+
+            let mut rendered = RenderedGraph::builder()
+                .graph(graph)
+                .border_style(border)
+                .build();
+            rendered.run_simulation();
+            let output = rendered.render_to_grid().to_string();
+            let ascii = unicode_to_svgbob(&output);
+            assert!(ascii.is_ascii(), "{}", ascii);
+            assert_eq!(svgbob_to_unicode(&ascii), output, "{}", ascii);
+        }
+    }
+
+    #[test]
+    fn test_ascii_stable() {
+        // 重叠绘制的边会产生不一致的转角，转换后再转回 ascii 应保持不变
+        let mut graph: DiGraph<&str, &str> = DiGraph::new();
+        let a = graph.add_node("Start");
+        let b = graph.add_node("Middle");
+        let c = graph.add_node("End");
+        graph.add_edge(a, b, "go");
+        graph.add_edge(a, c, "");
+        graph.add_edge(b, c, "");
+
+        let mut rendered = RenderedGraph::from_graph(graph);
+        rendered.run_simulation();
+        let ascii = unicode_to_svgbob(&rendered.render_to_grid().to_string());
+        assert_eq!(unicode_to_svgbob(&svgbob_to_unicode(&ascii)), ascii);
+    }
+}
@@ -0,0 +1,838 @@
+//! Graphviz DOT import and export.
+//!
+//! Only the subset of DOT that matters for flowcharts is understood: nodes,
+//! edges (including chains like `a -> b -> c`), `label`, `shape`, `style`,
+//! `peripheries`, `color` and `fontcolor` attributes, and `subgraph cluster_*`
+//! blocks. Everything else is parsed and ignored.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use ratatui::style::Color;
+
+use super::ab_graph::{BoxBorder, RenderedGraph, svg::css_color};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotNode {
+    pub id: String,
+    pub label: String,
+    pub border: BoxBorder,
+    pub color: Option<Color>,
+    pub font_color: Option<Color>,
+    // 所属的 cluster 名称
+    pub cluster: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotEdge {
+    pub src: String,
+    pub dst: String,
+    pub label: String,
+    pub color: Option<Color>,
+    pub font_color: Option<Color>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotCluster {
+    pub name: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DotGraph {
+    pub name: String,
+    pub nodes: Vec<DotNode>,
+    pub edges: Vec<DotEdge>,
+    pub clusters: Vec<DotCluster>,
+}
+
+impl DotGraph {
+    /// 解析 DOT 文本，只支持 digraph/graph 的常用子集
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let tokens = tokenize(text)?;
+        let mut parser = DotParser {
+            tokens,
+            pos: 0,
+            graph: DotGraph::default(),
+            node_index: HashMap::new(),
+        };
+        parser.parse_graph()?;
+        Ok(parser.graph)
+    }
+
+    pub fn node(&self, id: &str) -> Option<&DotNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// 输出为 DOT 文本
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        let name = if self.name.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", quote_id(&self.name))
+        };
+        let _ = writeln!(out, "digraph {}{{", name);
+        out.push_str("    node [shape=box];\n");
+
+        for cluster in self.clusters.iter() {
+            let _ = writeln!(out, "    subgraph {} {{", quote_id(&cluster.name));
+            if !cluster.label.is_empty() {
+                let _ = writeln!(out, "        label={};", quote_id(&cluster.label));
+            }
+            for node in self.nodes.iter() {
+                if node.cluster.as_ref() == Some(&cluster.name) {
+                    let _ = writeln!(out, "        {}", node_stmt(node));
+                }
+            }
+            out.push_str("    }\n");
+        }
+        for node in self.nodes.iter() {
+            if node.cluster.is_none() {
+                let _ = writeln!(out, "    {}", node_stmt(node));
+            }
+        }
+        for edge in self.edges.iter() {
+            let mut attrs: Vec<String> = Vec::new();
+            if !edge.label.is_empty() {
+                attrs.push(format!("label={}", quote_id(&edge.label)));
+            }
+            if let Some(color) = edge.color.and_then(color_name) {
+                attrs.push(format!("color={}", quote_id(&color)));
+            }
+            if let Some(color) = edge.font_color.and_then(color_name) {
+                attrs.push(format!("fontcolor={}", quote_id(&color)));
+            }
+            let _ = write!(
+                out,
+                "    {} -> {}",
+                quote_id(&edge.src),
+                quote_id(&edge.dst)
+            );
+            if !attrs.is_empty() {
+                let _ = write!(out, " [{}]", attrs.join(", "));
+            }
+            out.push_str(";\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// 转换为 petgraph，节点和边的权重为显示文本
+    pub fn to_digraph(&self) -> DiGraph<String, String> {
+        let mut graph: DiGraph<String, String> = DiGraph::new();
+        let mut index = HashMap::new();
+        for node in self.nodes.iter() {
+            // 节点框只能显示单行文本
+            let idx = graph.add_node(node.label.replace('\n', " "));
+            index.insert(node.id.clone(), idx);
+        }
+        for edge in self.edges.iter() {
+            if let (Some(&s), Some(&t)) = (index.get(&edge.src), index.get(&edge.dst)) {
+                graph.add_edge(s, t, edge.label.replace('\n', " "));
+            }
+        }
+        graph
+    }
+
+    /// 转换为可渲染的图，并带上 shape 和 color 对应的样式
+    pub fn to_rendered(&self) -> RenderedGraph<String, String> {
+        let mut rendered = RenderedGraph::from_graph(self.to_digraph());
+        let nodes: Vec<_> = rendered.graph().node_indices().collect();
+        for (node, idx) in self.nodes.iter().zip(nodes) {
+            rendered.set_node_border(idx, node.border);
+            if let Some(color) = node.color {
+                rendered.set_node_border_color(idx, color);
+            }
+            if let Some(color) = node.font_color {
+                rendered.set_node_text_color(idx, color);
+            }
+        }
+        let edges: Vec<_> = rendered.graph().edge_indices().collect();
+        let dot_edges = self
+            .edges
+            .iter()
+            .filter(|e| self.node(&e.src).is_some() && self.node(&e.dst).is_some());
+        for (edge, idx) in dot_edges.zip(edges) {
+            if let Some(color) = edge.color {
+                rendered.set_edge_color(idx, color);
+            }
+            if let Some(color) = edge.font_color {
+                rendered.set_edge_text_color(idx, color);
+            }
+        }
+        rendered
+    }
+}
+
+impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
+    /// Export the graph structure together with its styles as DOT.
+    pub fn to_dot(&self) -> String {
+        let mut dot = DotGraph::default();
+        let graph = self.graph();
+        for idx in graph.node_indices() {
+            let style = self.node_style(idx).cloned().unwrap_or_default();
+            dot.nodes.push(DotNode {
+                id: format!("n{}", idx.index()),
+                label: graph[idx].to_string(),
+                border: style.border,
+                color: Some(style.border_color),
+                font_color: Some(style.text_color),
+                cluster: None,
+            });
+        }
+        for edge in graph.edge_references() {
+            let style = self.edge_style(edge.id()).cloned().unwrap_or_default();
+            dot.edges.push(DotEdge {
+                src: format!("n{}", edge.source().index()),
+                dst: format!("n{}", edge.target().index()),
+                label: edge.weight().to_string(),
+                color: Some(style.line_color),
+                font_color: Some(style.text_color),
+            });
+        }
+        dot.to_dot()
+    }
+}
+
+fn node_stmt(node: &DotNode) -> String {
+    let mut attrs: Vec<String> = Vec::new();
+    if node.label != node.id {
+        attrs.push(format!("label={}", quote_id(&node.label)));
+    }
+    match node.border {
+        BoxBorder::Single => {}
+        BoxBorder::Rounded => attrs.push("style=rounded".to_string()),
+        BoxBorder::Double => attrs.push("peripheries=2".to_string()),
+        BoxBorder::Heavy => attrs.push("penwidth=2".to_string()),
+        BoxBorder::Ascii => attrs.push("shape=plaintext".to_string()),
+    }
+    if let Some(color) = node.color.and_then(color_name) {
+        attrs.push(format!("color={}", quote_id(&color)));
+    }
+    if let Some(color) = node.font_color.and_then(color_name) {
+        attrs.push(format!("fontcolor={}", quote_id(&color)));
+    }
+    if attrs.is_empty() {
+        format!("{};", quote_id(&node.id))
+    } else {
+        format!("{} [{}];", quote_id(&node.id), attrs.join(", "))
+    }
+}
+
+/// 给 DOT 的 ID 加上引号并转义
+fn quote_id(id: &str) -> String {
+    let mut out = String::from("\"");
+    for c in id.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// DOT 颜色名或者 #rrggbb 转换为终端颜色
+pub fn parse_color(text: &str) -> Option<Color> {
+    let text = text.trim().to_lowercase();
+    if let Some(hex) = text.strip_prefix('#') {
+        if hex.len() < 6 {
+            return None;
+        }
+        let v = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return Some(Color::Rgb(v(0)?, v(2)?, v(4)?));
+    }
+    let color = match text.as_str() {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "white" => Color::White,
+        "orange" => Color::Rgb(0xff, 0xa5, 0x00),
+        "purple" => Color::Rgb(0xa0, 0x20, 0xf0),
+        "brown" => Color::Rgb(0xa5, 0x2a, 0x2a),
+        "pink" => Color::Rgb(0xff, 0xc0, 0xcb),
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// 终端颜色转换为 DOT 颜色，命名色优先
+fn color_name(color: Color) -> Option<String> {
+    let name = match color {
+        Color::Black => "black",
+        Color::Red => "red",
+        Color::Green => "green",
+        Color::Yellow => "yellow",
+        Color::Blue => "blue",
+        Color::Magenta => "magenta",
+        Color::Cyan => "cyan",
+        Color::Gray => "gray",
+        Color::DarkGray => "darkgray",
+        Color::White => "white",
+        _ => return css_color(color),
+    };
+    Some(name.to_string())
+}
+
+fn border_from_attrs(attrs: &HashMap<String, String>) -> BoxBorder {
+    let shape = attrs
+        .get("shape")
+        .map(|s| s.to_lowercase())
+        .unwrap_or_default();
+    let style = attrs
+        .get("style")
+        .map(|s| s.to_lowercase())
+        .unwrap_or_default();
+    let peripheries = attrs
+        .get("peripheries")
+        .and_then(|p| p.trim().parse::<usize>().ok())
+        .unwrap_or(1);
+    if peripheries > 1 || shape.starts_with("double") {
+        return BoxBorder::Double;
+    }
+    if style.contains("rounded") {
+        return BoxBorder::Rounded;
+    }
+    let penwidth = attrs
+        .get("penwidth")
+        .and_then(|p| p.trim().parse::<f64>().ok())
+        .unwrap_or(1.0);
+    if penwidth >= 2.0 || style.contains("bold") {
+        return BoxBorder::Heavy;
+    }
+    match shape.as_str() {
+        "ellipse" | "oval" | "circle" | "mrecord" => BoxBorder::Rounded,
+        "plaintext" | "plain" | "none" => BoxBorder::Ascii,
+        _ => BoxBorder::Single,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    // 未加引号的 ID，可能是关键字
+    Id(String),
+    // 加引号或者 <...> 形式的 ID
+    Quoted(String),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Semi,
+    Comma,
+    Eq,
+    Colon,
+    Edge,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line_start = true;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        // # 开头的行是预处理输出，忽略
+        if c == '#' && line_start {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        line_start = false;
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+            continue;
+        }
+        match c {
+            '{' => tokens.push(Token::LBrace),
+            '}' => tokens.push(Token::RBrace),
+            '[' => tokens.push(Token::LBracket),
+            ']' => tokens.push(Token::RBracket),
+            ';' => tokens.push(Token::Semi),
+            ',' => tokens.push(Token::Comma),
+            '=' => tokens.push(Token::Eq),
+            ':' => tokens.push(Token::Colon),
+            '-' if matches!(chars.get(i + 1), Some('>') | Some('-')) => {
+                tokens.push(Token::Edge);
+                i += 1;
+            }
+            '"' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err("unterminated string".to_string()),
+                        Some('"') => break,
+                        Some('\\') => {
+                            match chars.get(i + 1) {
+                                Some('n') | Some('l') | Some('r') => s.push('\n'),
+                                Some('"') => s.push('"'),
+                                Some('\\') => s.push('\\'),
+                                Some('\n') => {}
+                                Some(other) => {
+                                    s.push('\\');
+                                    s.push(*other);
+                                }
+                                None => return Err("unterminated string".to_string()),
+                            }
+                            i += 2;
+                            continue;
+                        }
+                        Some(ch) => s.push(*ch),
+                    }
+                    i += 1;
+                }
+                // 去掉 label 末尾 \l 之类产生的换行
+                tokens.push(Token::Quoted(s.trim_end_matches('\n').to_string()));
+            }
+            '<' => {
+                let mut depth = 0;
+                let mut s = String::new();
+                loop {
+                    match chars.get(i) {
+                        None => return Err("unterminated html string".to_string()),
+                        Some('<') => {
+                            depth += 1;
+                            if depth > 1 {
+                                s.push('<');
+                            }
+                        }
+                        Some('>') => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                            s.push('>');
+                        }
+                        Some(ch) => s.push(*ch),
+                    }
+                    i += 1;
+                }
+                tokens.push(Token::Quoted(s));
+            }
+            _ if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut s = String::new();
+                while i < chars.len() {
+                    let ch = chars[i];
+                    let is_edge = ch == '-' && matches!(chars.get(i + 1), Some('>') | Some('-'));
+                    if is_edge || !(ch.is_alphanumeric() || ch == '_' || ch == '.' || ch == '-') {
+                        break;
+                    }
+                    s.push(ch);
+                    i += 1;
+                }
+                tokens.push(Token::Id(s));
+                continue;
+            }
+            _ => return Err(format!("unexpected character '{}'", c)),
+        }
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+struct DotParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    graph: DotGraph,
+    node_index: HashMap<String, usize>,
+}
+
+/// 当前作用域的默认属性
+#[derive(Clone, Default)]
+struct Scope {
+    node: HashMap<String, String>,
+    edge: HashMap<String, String>,
+    cluster: Option<String>,
+}
+
+impl DotParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Id(s)) if s.eq_ignore_ascii_case(keyword))
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), String> {
+        match self.next() {
+            Some(t) if t == token => Ok(()),
+            other => Err(format!("expected {:?}, found {:?}", token, other)),
+        }
+    }
+
+    fn id(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Id(s)) | Some(Token::Quoted(s)) => Ok(s),
+            other => Err(format!("expected identifier, found {:?}", other)),
+        }
+    }
+
+    fn parse_graph(&mut self) -> Result<(), String> {
+        if self.is_keyword("strict") {
+            self.pos += 1;
+        }
+        if self.is_keyword("digraph") || self.is_keyword("graph") {
+            self.pos += 1;
+        } else {
+            return Err("expected 'digraph' or 'graph'".to_string());
+        }
+        if !matches!(self.peek(), Some(Token::LBrace)) {
+            self.graph.name = self.id()?;
+        }
+        self.expect(Token::LBrace)?;
+        let mut scope = Scope::default();
+        self.parse_stmt_list(&mut scope)?;
+        Ok(())
+    }
+
+    /// 解析语句直到遇到 `}`，返回语句中出现的所有节点
+    fn parse_stmt_list(&mut self, scope: &mut Scope) -> Result<Vec<String>, String> {
+        let mut members = Vec::new();
+        loop {
+            match self.peek() {
+                None => return Err("unexpected end of input".to_string()),
+                Some(Token::RBrace) => {
+                    self.pos += 1;
+                    return Ok(members);
+                }
+                Some(Token::Semi) => {
+                    self.pos += 1;
+                }
+                _ => {
+                    let nodes = self.parse_stmt(scope)?;
+                    members.extend(nodes);
+                }
+            }
+        }
+    }
+
+    fn parse_stmt(&mut self, scope: &mut Scope) -> Result<Vec<String>, String> {
+        // 默认属性
+        for keyword in ["node", "edge", "graph"] {
+            if self.is_keyword(keyword)
+                && matches!(self.tokens.get(self.pos + 1), Some(Token::LBracket))
+            {
+                self.pos += 1;
+                let attrs = self.parse_attr_lists()?;
+                match keyword {
+                    "node" => scope.node.extend(attrs),
+                    "edge" => scope.edge.extend(attrs),
+                    _ => self.apply_graph_attrs(scope, attrs),
+                }
+                return Ok(Vec::new());
+            }
+        }
+
+        let operand = self.parse_operand(scope)?;
+        // 图属性 ID = ID
+        if matches!(self.peek(), Some(Token::Eq)) {
+            self.pos += 1;
+            let value = self.id()?;
+            if let [key] = operand.as_slice() {
+                let mut attrs = HashMap::new();
+                attrs.insert(key.to_lowercase(), value);
+                self.apply_graph_attrs(scope, attrs);
+            }
+            return Ok(Vec::new());
+        }
+
+        let mut operands = vec![operand];
+        while matches!(self.peek(), Some(Token::Edge)) {
+            self.pos += 1;
+            operands.push(self.parse_operand(scope)?);
+        }
+        let attrs = if matches!(self.peek(), Some(Token::LBracket)) {
+            self.parse_attr_lists()?
+        } else {
+            HashMap::new()
+        };
+
+        let mut members: Vec<String> = operands.concat();
+        if operands.len() == 1 {
+            // 节点语句
+            for id in operands[0].iter() {
+                self.touch_node(id, scope, &attrs);
+            }
+        } else {
+            let mut edge_attrs = scope.edge.clone();
+            edge_attrs.extend(attrs);
+            for pair in operands.windows(2) {
+                for src in pair[0].iter() {
+                    for dst in pair[1].iter() {
+                        self.add_edge(src, dst, &edge_attrs);
+                    }
+                }
+            }
+        }
+        members.dedup();
+        Ok(members)
+    }
+
+    /// 节点 ID（可以带端口）或者子图
+    fn parse_operand(&mut self, scope: &mut Scope) -> Result<Vec<String>, String> {
+        if self.is_keyword("subgraph") || matches!(self.peek(), Some(Token::LBrace)) {
+            return self.parse_subgraph(scope);
+        }
+        let id = self.id()?;
+        // 忽略端口
+        while matches!(self.peek(), Some(Token::Colon)) {
+            self.pos += 1;
+            self.id()?;
+        }
+        if !matches!(self.peek(), Some(Token::Eq)) {
+            self.touch_node(&id, scope, &HashMap::new());
+        }
+        Ok(vec![id])
+    }
+
+    fn parse_subgraph(&mut self, scope: &mut Scope) -> Result<Vec<String>, String> {
+        let mut name = String::new();
+        if self.is_keyword("subgraph") {
+            self.pos += 1;
+            if !matches!(self.peek(), Some(Token::LBrace)) {
+                name = self.id()?;
+            }
+        }
+        self.expect(Token::LBrace)?;
+        let mut inner = scope.clone();
+        if name.starts_with("cluster") {
+            self.graph.clusters.push(DotCluster {
+                name: name.clone(),
+                label: String::new(),
+            });
+            inner.cluster = Some(name);
+        }
+        self.parse_stmt_list(&mut inner)
+    }
+
+    fn parse_attr_lists(&mut self) -> Result<HashMap<String, String>, String> {
+        let mut attrs = HashMap::new();
+        while matches!(self.peek(), Some(Token::LBracket)) {
+            self.pos += 1;
+            loop {
+                match self.peek() {
+                    Some(Token::RBracket) => {
+                        self.pos += 1;
+                        break;
+                    }
+                    Some(Token::Comma) | Some(Token::Semi) => {
+                        self.pos += 1;
+                    }
+                    _ => {
+                        let key = self.id()?;
+                        if matches!(self.peek(), Some(Token::Eq)) {
+                            self.pos += 1;
+                            let value = self.id()?;
+                            attrs.insert(key.to_lowercase(), value);
+                        } else {
+                            attrs.insert(key.to_lowercase(), "true".to_string());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(attrs)
+    }
+
+    fn apply_graph_attrs(&mut self, scope: &Scope, attrs: HashMap<String, String>) {
+        let Some(label) = attrs.get("label") else {
+            return;
+        };
+        if let Some(name) = &scope.cluster
+            && let Some(cluster) = self.graph.clusters.iter_mut().find(|c| &c.name == name)
+        {
+            cluster.label = label.clone();
+        }
+    }
+
+    /// 新建节点或者更新已有节点的属性
+    fn touch_node(&mut self, id: &str, scope: &Scope, attrs: &HashMap<String, String>) {
+        let idx = match self.node_index.get(id) {
+            Some(idx) => *idx,
+            None => {
+                let mut node_attrs = scope.node.clone();
+                node_attrs.extend(attrs.clone());
+                self.graph.nodes.push(DotNode {
+                    id: id.to_string(),
+                    label: id.to_string(),
+                    border: BoxBorder::Single,
+                    color: None,
+                    font_color: None,
+                    cluster: scope.cluster.clone(),
+                });
+                let idx = self.graph.nodes.len() - 1;
+                self.node_index.insert(id.to_string(), idx);
+                self.apply_node_attrs(idx, &node_attrs);
+                return;
+            }
+        };
+        if !attrs.is_empty() {
+            // 已存在节点的 shape 需要和原有属性合并
+            self.apply_node_attrs(idx, attrs);
+        }
+        if self.graph.nodes[idx].cluster.is_none() {
+            self.graph.nodes[idx].cluster = scope.cluster.clone();
+        }
+    }
+
+    fn apply_node_attrs(&mut self, idx: usize, attrs: &HashMap<String, String>) {
+        let node = &mut self.graph.nodes[idx];
+        if let Some(label) = attrs.get("label") {
+            // \N 表示节点名
+            node.label = label.replace("\\N", &node.id);
+        }
+        if ["shape", "style", "peripheries"]
+            .iter()
+            .any(|k| attrs.contains_key(*k))
+        {
+            node.border = border_from_attrs(attrs);
+        }
+        if let Some(color) = attrs.get("color").and_then(|c| parse_color(c)) {
+            node.color = Some(color);
+        }
+        if let Some(color) = attrs.get("fontcolor").and_then(|c| parse_color(c)) {
+            node.font_color = Some(color);
+        }
+    }
+
+    fn add_edge(&mut self, src: &str, dst: &str, attrs: &HashMap<String, String>) {
+        self.graph.edges.push(DotEdge {
+            src: src.to_string(),
+            dst: dst.to_string(),
+            label: attrs
+                .get("label")
+                .or_else(|| attrs.get("xlabel"))
+                .cloned()
+                .unwrap_or_default(),
+            color: attrs.get("color").and_then(|c| parse_color(c)),
+            font_color: attrs.get("fontcolor").and_then(|c| parse_color(c)),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r##"
+        digraph build {
+            node [shape=box, color=blue];
+            // comment
+            subgraph cluster_core {
+                label = "Core";
+                parse [label="Parse\nInput"];
+                check;
+            }
+            link [shape=ellipse, color="#ff0000"];
+            parse -> check -> link [label="ok"];
+            check -> { report log } [color=green];
+        }
+    "##;
+
+    #[test]
+    fn test_parse_dot() {
+        let g = DotGraph::parse(SAMPLE).unwrap();
+        assert_eq!(g.name, "build");
+        assert_eq!(g.nodes.len(), 5);
+        assert_eq!(g.edges.len(), 4);
+
+        let parse = g.node("parse").unwrap();
+        assert_eq!(parse.label, "Parse\nInput");
+        assert_eq!(parse.color, Some(Color::Blue));
+        assert_eq!(parse.cluster.as_deref(), Some("cluster_core"));
+
+        let link = g.node("link").unwrap();
+        assert_eq!(link.border, BoxBorder::Rounded);
+        assert_eq!(link.color, Some(Color::Rgb(0xff, 0, 0)));
+        assert_eq!(link.cluster, None);
+
+        assert_eq!(g.clusters.len(), 1);
+        assert_eq!(g.clusters[0].label, "Core");
+
+        assert_eq!(g.edges[0].label, "ok");
+        assert_eq!(g.edges[1].label, "ok");
+        assert_eq!(
+            (g.edges[2].src.as_str(), g.edges[2].dst.as_str()),
+            ("check", "report")
+        );
+        assert_eq!(g.edges[3].color, Some(Color::Green));
+    }
+
+    #[test]
+    fn test_dot_round_trip() {
+        let g = DotGraph::parse(SAMPLE).unwrap();
+        let text = g.to_dot();
+        let g2 = DotGraph::parse(&text).unwrap();
+        assert_eq!(g, g2);
+    }
+
+    #[test]
+    fn test_dot_errors() {
+        assert!(DotGraph::parse("digraph { a -> }").is_err());
+        assert!(DotGraph::parse("flowchart { a }").is_err());
+        assert!(DotGraph::parse("digraph { a [label=\"x }").is_err());
+    }
+
+    #[test]
+    fn test_quote_id() {
+        assert_eq!(quote_id(r#"C:\dir "x""#), r#""C:\\dir \"x\"""#);
+        let g = DotGraph::parse(r#"digraph { a [label="C:\\dir \"x\""] }"#).unwrap();
+        assert_eq!(g.nodes[0].label, r#"C:\dir "x""#);
+        assert_eq!(DotGraph::parse(&g.to_dot()).unwrap(), g);
+    }
+
+    #[test]
+    fn test_dot_to_ascii() {
+        let g = DotGraph::parse("digraph { a [label=Start]; b [label=End]; a -> b [label=go] }")
+            .unwrap();
+        let mut rendered = g.to_rendered();
+        rendered.run_simulation();
+        let output = rendered.render_to_grid().to_string();
+        assert!(output.contains("Start"));
+        assert!(output.contains("End"));
+        assert!(output.contains("go"));
+
+        let dot = rendered.to_dot();
+        let back = DotGraph::parse(&dot).unwrap();
+        assert_eq!(back.nodes.len(), 2);
+        assert_eq!(back.edges[0].label, "go");
+    }
+}
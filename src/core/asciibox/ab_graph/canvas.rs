@@ -0,0 +1,59 @@
+//! Common drawing interface shared by the character grids.
+
+use ratatui::style::Color;
+
+use super::style::BorderChars;
+
+/// A surface of character cells that layout and rendering code can draw on.
+///
+/// Implementors only have to provide [`Canvas::set_char`]; lines, text and
+/// boxes are built on top of it. Backends without colors simply ignore `fg`.
+pub trait Canvas {
+    /// Set a character at (x, y) with a color.
+    fn set_char(&mut self, x: usize, y: usize, c: char, fg: Color);
+
+    /// Draw a horizontal line.
+    fn draw_hline(&mut self, x: usize, y: usize, len: usize, c: char, fg: Color) {
+        for i in 0..len {
+            self.set_char(x + i, y, c, fg);
+        }
+    }
+
+    /// Draw a vertical line.
+    fn draw_vline(&mut self, x: usize, y: usize, len: usize, c: char, fg: Color) {
+        for i in 0..len {
+            self.set_char(x, y + i, c, fg);
+        }
+    }
+
+    /// Draw text at position, one cell per character.
+    fn draw_text(&mut self, x: usize, y: usize, text: &str, fg: Color) {
+        for (i, c) in text.chars().enumerate() {
+            self.set_char(x + i, y, c, fg);
+        }
+    }
+
+    /// Draw the outline of a box. The interior is left untouched.
+    fn draw_box(
+        &mut self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        chars: &BorderChars,
+        fg: Color,
+    ) {
+        if width < 2 || height < 2 {
+            return;
+        }
+        let (right, bottom) = (x + width - 1, y + height - 1);
+        self.draw_hline(x + 1, y, width - 2, chars.horizontal, fg);
+        self.draw_hline(x + 1, bottom, width - 2, chars.horizontal, fg);
+        self.draw_vline(x, y + 1, height - 2, chars.vertical, fg);
+        self.draw_vline(right, y + 1, height - 2, chars.vertical, fg);
+        self.set_char(x, y, chars.top_left, fg);
+        self.set_char(right, y, chars.top_right, fg);
+        self.set_char(x, bottom, chars.bottom_left, fg);
+        self.set_char(right, bottom, chars.bottom_right, fg);
+    }
+}
@@ -65,7 +65,7 @@ where
 ///
 /// ```rust
 /// use petgraph::graph::DiGraph;
-/// use ascii_petgraph::AsciiGraphExt;
+/// use asciibox::core::asciibox::ab_graph::AsciiGraphExt;
 ///
 /// let mut graph = DiGraph::new();
 /// let a = graph.add_node("Start");
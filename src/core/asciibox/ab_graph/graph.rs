@@ -1,17 +1,22 @@
 //! Core `RenderedGraph` type that ties together physics, rendering, and styling.
 
+use std::collections::VecDeque;
 use std::fmt::Display;
 
 use petgraph::graph::{DiGraph, EdgeIndex, NodeIndex};
 use petgraph::visit::EdgeRef;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
+use ratatui::style::{Color, Style};
 use ratatui::widgets::Widget;
 
-use super::physics::{PhysicsConfig, PhysicsEngine};
+use super::canvas::Canvas;
+use super::physics::{PhysicsConfig, PhysicsEngine, Vec2};
 use super::render::{CharGrid, GraphRenderer, RenderedEdge, RenderedNode};
+use super::selection::{NavKey, SelectionEvent, SelectionStyle};
 use super::style::{BoxBorder, EdgeStyle, NodeStyle};
+use super::svg::SvgRenderer;
+use super::viewport::Viewport;
 
 /// A rendered graph ready for display in a TUI.
 ///
@@ -38,6 +43,16 @@ pub struct RenderedGraph<N, E> {
     rendered_edges: Vec<RenderedEdge<()>>,
     /// Whether layout is dirty and needs recalculation.
     layout_dirty: bool,
+    /// Currently selected node.
+    selected: Option<NodeIndex>,
+    /// Highlight for the selected node and its incident edges.
+    selection_style: SelectionStyle,
+    /// Selection events not yet taken by the host.
+    selection_events: VecDeque<SelectionEvent>,
+    /// Visible part of the grid in the widget.
+    viewport: Viewport,
+    /// Draw a legend when labels are replaced by acronyms or IDs.
+    show_legend: bool,
 }
 
 impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
@@ -61,6 +76,11 @@ impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
             rendered_nodes: Vec::new(),
             rendered_edges: Vec::new(),
             layout_dirty: true,
+            selected: None,
+            selection_style: SelectionStyle::default(),
+            selection_events: VecDeque::new(),
+            viewport: Viewport::default(),
+            show_legend: true,
         }
     }
 
@@ -85,11 +105,34 @@ impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
     }
 
     /// Advance the physics simulation by one step.
+    ///
+    /// Positions are kept normalized, so hosts can render after every tick
+    /// to animate the layout.
     pub fn tick(&mut self) {
         self.physics.tick(&self.graph);
+        self.physics.normalize_positions();
         self.layout_dirty = true;
     }
 
+    /// Advance the simulation by up to `steps` ticks unless it is stable.
+    ///
+    /// Returns whether the simulation is still moving, i.e. whether the host
+    /// should keep redrawing.
+    pub fn animate(&mut self, steps: usize) -> bool {
+        for _ in 0..steps {
+            if self.is_stable() {
+                break;
+            }
+            self.tick();
+        }
+        !self.is_stable()
+    }
+
+    /// Let the layout settle again, e.g. after a node was moved.
+    pub fn reheat(&mut self) {
+        self.physics.reheat();
+    }
+
     /// Check if the simulation has converged.
     pub fn is_stable(&self) -> bool {
         self.physics.is_stable()
@@ -107,6 +150,38 @@ impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
         self.physics.iterations()
     }
 
+    /// Pin or unpin a node. Pinned nodes are not moved by the simulation.
+    pub fn set_node_pinned(&mut self, node: NodeIndex, pinned: bool) {
+        if node.index() < self.graph.node_count() {
+            self.physics.set_pinned(node, pinned);
+            self.physics.reheat();
+        }
+    }
+
+    /// Check if a node is pinned.
+    pub fn is_node_pinned(&self, node: NodeIndex) -> bool {
+        node.index() < self.graph.node_count() && self.physics.is_pinned(node)
+    }
+
+    /// Drag a node by (dx, dy) grid cells.
+    ///
+    /// The node is pinned at its new position and the simulation is reheated
+    /// so that the rest of the layout settles around it.
+    pub fn drag_node(&mut self, node: NodeIndex, dx: isize, dy: isize) {
+        if node.index() >= self.graph.node_count() {
+            return;
+        }
+        let delta = Vec2::new(
+            dx as f64 / (self.renderer.scale_x * self.renderer.zoom),
+            dy as f64 / (self.renderer.scale_y * self.renderer.zoom),
+        );
+        self.physics.set_pinned(node, true);
+        self.physics.move_by(node, delta);
+        self.physics.normalize_positions();
+        self.physics.reheat();
+        self.layout_dirty = true;
+    }
+
     // === Color API ===
 
     /// Helper to mutate a node's style.
@@ -227,6 +302,21 @@ impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
         self.mutate_all_node_styles(|style| style.border = border);
     }
 
+    /// Set the box border style of a single node.
+    pub fn set_node_border(&mut self, node: NodeIndex, border: BoxBorder) {
+        self.mutate_node_style(node, |style| style.border = border);
+    }
+
+    /// Get the current style of a node.
+    pub fn node_style(&self, node: NodeIndex) -> Option<&NodeStyle> {
+        self.node_styles.get(node.index())
+    }
+
+    /// Get the current style of an edge.
+    pub fn edge_style(&self, edge: EdgeIndex) -> Option<&EdgeStyle> {
+        self.edge_styles.get(edge.index())
+    }
+
     /// Set the scaling mode for handling large graphs.
     pub fn set_scaling_mode(&mut self, mode: super::render::ScalingMode) {
         self.renderer.scaling_mode = mode;
@@ -235,45 +325,281 @@ impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
 
     /// Auto-detect and apply appropriate scaling mode based on terminal width.
     pub fn auto_scale(&mut self, max_width: usize) {
+        self.auto_scale_to(max_width, usize::MAX);
+    }
+
+    /// Pick the mildest scaling that fits the layout in `max_width` x
+    /// `max_height` cells.
+    ///
+    /// For each scaling mode, from full labels over abbreviations and
+    /// acronyms to numeric IDs, the layout is squeezed as long as this does
+    /// not make nodes overlap. If nothing fits, numeric IDs with the tightest
+    /// layout are used. The legend is not counted, it goes below the graph.
+    pub fn auto_scale_to(&mut self, max_width: usize, max_height: usize) {
         use super::render::ScalingMode;
 
-        // First try full labels
-        self.renderer.scaling_mode = ScalingMode::Full;
+        const MODES: [ScalingMode; 5] = [
+            ScalingMode::Full,
+            ScalingMode::Abbreviate(12),
+            ScalingMode::Abbreviate(8),
+            ScalingMode::Acronym,
+            ScalingMode::NumericIds,
+        ];
+        const ZOOMS: [f64; 5] = [1.0, 0.85, 0.7, 0.55, 0.4];
+
+        let mut zoom = 1.0;
+        for mode in MODES {
+            self.renderer.scaling_mode = mode;
+            zoom = 1.0;
+            let mut overlaps = None;
+            for z in ZOOMS {
+                self.renderer.zoom = z;
+                self.layout_dirty = true;
+                self.update_layout();
+
+                // Overlaps already present in the unsqueezed layout are fine
+                let count = self.overlap_count();
+                if count > *overlaps.get_or_insert(count) {
+                    break;
+                }
+                zoom = z;
+                let (width, height) = self.layout_size();
+                if width <= max_width && height <= max_height {
+                    return;
+                }
+            }
+        }
+        self.renderer.zoom = zoom;
         self.layout_dirty = true;
-        self.update_layout();
+    }
 
-        let current_width = self
+    /// Size of the laid out nodes including padding, without edge labels.
+    fn layout_size(&self) -> (usize, usize) {
+        let width = self
             .rendered_nodes
             .iter()
             .map(|n| n.x + n.width)
             .max()
-            .unwrap_or(0)
-            + self.renderer.padding;
+            .unwrap_or(0);
+        let height = self
+            .rendered_nodes
+            .iter()
+            .map(|n| n.y + n.height)
+            .max()
+            .unwrap_or(0);
+        (
+            width + self.renderer.padding,
+            height + self.renderer.padding,
+        )
+    }
 
-        if current_width <= max_width {
-            return;
+    /// Number of node pairs that touch or overlap.
+    fn overlap_count(&self) -> usize {
+        let nodes = &self.rendered_nodes;
+        let mut count = 0;
+        for (i, a) in nodes.iter().enumerate() {
+            for b in &nodes[i + 1..] {
+                if a.x < b.x + b.width + 1
+                    && b.x < a.x + a.width + 1
+                    && a.y < b.y + b.height + 1
+                    && b.y < a.y + a.height + 1
+                {
+                    count += 1;
+                }
+            }
         }
+        count
+    }
 
-        // Try truncating labels
-        self.renderer.scaling_mode = ScalingMode::Truncate(8);
-        self.layout_dirty = true;
-        self.update_layout();
+    /// Show the legend below the graph when labels are replaced by IDs.
+    pub fn set_show_legend(&mut self, show: bool) {
+        self.show_legend = show;
+    }
 
-        let current_width = self
-            .rendered_nodes
+    /// Legend entries `(shown label, full label)` for the current scaling mode.
+    ///
+    /// Empty unless the mode replaces labels with acronyms or numeric IDs.
+    pub fn legend(&self) -> Vec<(String, String)> {
+        if !self.renderer.scaling_mode.needs_legend() {
+            return Vec::new();
+        }
+        self.graph
+            .node_indices()
+            .map(|n| {
+                let label = self.graph[n].to_string();
+                (self.renderer.display_label(n, &label), label)
+            })
+            .filter(|(shown, label)| shown != label)
+            .collect()
+    }
+
+    /// Lines of the legend block, empty when there is nothing to explain.
+    fn legend_lines(&self) -> Vec<String> {
+        if !self.show_legend {
+            return Vec::new();
+        }
+        let legend = self.legend();
+        if legend.is_empty() {
+            return Vec::new();
+        }
+        let id_width = legend
             .iter()
-            .map(|n| n.x + n.width)
+            .map(|(shown, _)| shown.chars().count())
             .max()
-            .unwrap_or(0)
-            + self.renderer.padding;
+            .unwrap_or(0);
+        let mut lines = vec!["Legend:".to_string()];
+        for (shown, label) in legend {
+            lines.push(format!("  {:<w$}  {}", shown, label, w = id_width));
+        }
+        lines
+    }
+
+    // === Selection API ===
 
-        if current_width <= max_width {
+    /// Get the selected node.
+    pub fn selected(&self) -> Option<NodeIndex> {
+        self.selected
+    }
+
+    /// Select a node, or clear the selection with `None`.
+    ///
+    /// Emits a [`SelectionEvent`] when the selection actually changes.
+    /// Indices outside the graph clear the selection.
+    pub fn select(&mut self, node: Option<NodeIndex>) {
+        let node = node.filter(|n| n.index() < self.graph.node_count());
+        if node == self.selected {
             return;
         }
+        self.selected = node;
+        self.selection_events.push_back(match node {
+            Some(node) => SelectionEvent::Selected(node),
+            None => SelectionEvent::Cleared,
+        });
+    }
+
+    /// Clear the selection.
+    pub fn clear_selection(&mut self) {
+        self.select(None);
+    }
+
+    /// Report the selected node as activated, e.g. when Enter is pressed.
+    pub fn activate_selection(&mut self) {
+        if let Some(node) = self.selected {
+            self.selection_events
+                .push_back(SelectionEvent::Activated(node));
+        }
+    }
 
-        // Fall back to numeric IDs
-        self.renderer.scaling_mode = ScalingMode::NumericIds;
-        self.layout_dirty = true;
+    /// Move the selection with a navigation key. Returns whether it changed.
+    ///
+    /// Arrow keys move to the nearest node in that direction connected to
+    /// the selected node by an edge (in either direction), falling back to
+    /// any node in that direction. Next/Prev cycle through all nodes.
+    /// Without a selection, any key selects the first node.
+    pub fn move_selection(&mut self, key: NavKey) -> bool {
+        let count = self.graph.node_count();
+        if count == 0 {
+            return false;
+        }
+        let before = self.selected;
+        let target = match (self.selected, key) {
+            (None, _) => NodeIndex::new(0),
+            (Some(node), NavKey::Next) => NodeIndex::new((node.index() + 1) % count),
+            (Some(node), NavKey::Prev) => NodeIndex::new((node.index() + count - 1) % count),
+            (Some(node), _) => {
+                self.update_layout();
+                let neighbors: Vec<NodeIndex> = self.graph.neighbors_undirected(node).collect();
+                match self
+                    .nearest_in_direction(node, key, &neighbors)
+                    .or_else(|| {
+                        let all: Vec<NodeIndex> = self.graph.node_indices().collect();
+                        self.nearest_in_direction(node, key, &all)
+                    }) {
+                    Some(target) => target,
+                    None => return false,
+                }
+            }
+        };
+        self.select(Some(target));
+        self.selected != before
+    }
+
+    /// Nearest candidate in the direction of `key`, measured between node centers.
+    fn nearest_in_direction(
+        &self,
+        from: NodeIndex,
+        key: NavKey,
+        candidates: &[NodeIndex],
+    ) -> Option<NodeIndex> {
+        let center = |node: NodeIndex| {
+            self.rendered_nodes
+                .get(node.index())
+                .map(|n| ((n.x + n.width / 2) as isize, (n.y + n.height / 2) as isize))
+        };
+        let (fx, fy) = center(from)?;
+        candidates
+            .iter()
+            .filter(|&&c| c != from)
+            .filter_map(|&c| {
+                let (cx, cy) = center(c)?;
+                key.score(cx - fx, cy - fy).map(|score| (score, c))
+            })
+            .min()
+            .map(|(_, c)| c)
+    }
+
+    /// Take all pending selection events, oldest first.
+    pub fn take_selection_events(&mut self) -> Vec<SelectionEvent> {
+        self.selection_events.drain(..).collect()
+    }
+
+    /// Get the highlight style used for the selection.
+    pub fn selection_style(&self) -> &SelectionStyle {
+        &self.selection_style
+    }
+
+    /// Set the highlight style used for the selection.
+    pub fn set_selection_style(&mut self, style: SelectionStyle) {
+        self.selection_style = style;
+    }
+
+    // === Viewport API ===
+
+    /// Get the viewport used by the widget.
+    pub fn viewport(&self) -> &Viewport {
+        &self.viewport
+    }
+
+    /// Get the viewport for scrolling or toggling the minimap.
+    pub fn viewport_mut(&mut self) -> &mut Viewport {
+        &mut self.viewport
+    }
+
+    /// Scroll the viewport by (dx, dy) cells.
+    pub fn pan(&mut self, dx: isize, dy: isize) {
+        self.viewport.scroll_by(dx, dy);
+    }
+
+    /// Show or hide the minimap overlay.
+    pub fn set_minimap(&mut self, show: bool) {
+        self.viewport.minimap = show;
+    }
+
+    /// Scroll so that the node is in the middle of the viewport.
+    pub fn center_on_node(&mut self, node: NodeIndex) {
+        self.update_layout();
+        if let Some(n) = self.rendered_nodes.get(node.index()) {
+            self.viewport
+                .center_on(n.x + n.width / 2, n.y + n.height / 2);
+        }
+    }
+
+    /// Scroll as little as possible so that the whole node is visible.
+    pub fn reveal_node(&mut self, node: NodeIndex) {
+        self.update_layout();
+        if let Some(n) = self.rendered_nodes.get(node.index()) {
+            self.viewport.reveal(n.x, n.y, n.width, n.height);
+        }
     }
 
     // === Rendering ===
@@ -294,8 +620,7 @@ impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
             let display_label = self.renderer.display_label(node_idx, &label);
             let width = self.renderer.node_width(&display_label);
 
-            let x = (pos.x * self.renderer.scale_x) as usize + self.renderer.padding;
-            let y = (pos.y * self.renderer.scale_y) as usize + self.renderer.padding;
+            let (x, y) = self.renderer.to_grid(pos.x, pos.y);
 
             self.rendered_nodes.push(RenderedNode {
                 index: node_idx,
@@ -338,25 +663,25 @@ impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
         for edge in self.graph.edge_references() {
             let idx = edge.id().index();
             let offset = edge_offsets.get(&edge.id()).copied().unwrap_or(0);
-            self.rendered_edges.push(RenderedEdge {
+            let mut rendered = RenderedEdge {
                 index: edge.id(),
                 label: (),
                 source: edge.source(),
                 target: edge.target(),
-                path: Vec::new(), // Path calculated during rendering
+                path: Vec::new(),
                 style: self.edge_styles.get(idx).cloned().unwrap_or_default(),
                 parallel_offset: offset,
-            });
+            };
+            rendered.path = self.renderer.route_edge(&rendered, &self.rendered_nodes);
+            self.rendered_edges.push(rendered);
         }
 
         self.layout_dirty = false;
     }
 
-    /// Render the graph to a character grid.
-    pub fn render_to_grid(&mut self) -> CharGrid {
-        self.update_layout();
-
-        // Calculate grid size with extra room for edge labels
+    /// Size of the character grid needed to hold the current layout.
+    fn grid_size(&self) -> (usize, usize) {
+        // Extra room for edge labels
         let max_label_len = self
             .graph
             .edge_weights()
@@ -382,11 +707,15 @@ impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
             + self.renderer.padding
             + 2;
 
-        let mut grid = CharGrid::new(max_x.max(1), max_y.max(1));
+        (max_x.max(1), max_y.max(1))
+    }
 
-        // Render edges first (so nodes draw on top)
-        for (idx, edge) in self.rendered_edges.iter().enumerate() {
-            let edge_with_label = RenderedEdge {
+    /// Cached edges with their labels and current styles attached.
+    fn labeled_edges(&self) -> Vec<RenderedEdge<String>> {
+        self.rendered_edges
+            .iter()
+            .enumerate()
+            .map(|(idx, edge)| RenderedEdge {
                 index: edge.index,
                 label: self
                     .graph
@@ -396,36 +725,115 @@ impl<N: Display + Clone, E: Display + Clone> RenderedGraph<N, E> {
                 source: edge.source,
                 target: edge.target,
                 path: edge.path.clone(),
-                style: self.edge_styles.get(idx).cloned().unwrap_or_default(),
+                style: if self
+                    .selected
+                    .is_some_and(|n| n == edge.source || n == edge.target)
+                {
+                    self.selection_style.edge.clone()
+                } else {
+                    self.edge_styles.get(idx).cloned().unwrap_or_default()
+                },
                 parallel_offset: edge.parallel_offset,
-            };
-            self.renderer
-                .render_edge(&mut grid, &edge_with_label, &self.rendered_nodes);
-        }
+            })
+            .collect()
+    }
 
-        // Render nodes
-        for (idx, node) in self.rendered_nodes.iter().enumerate() {
-            let label = self
-                .renderer
-                .display_label(node.index, &self.graph[node.index]);
-            let node_with_label = RenderedNode {
+    /// Cached nodes with their display labels and current styles attached.
+    fn labeled_nodes(&self) -> Vec<RenderedNode<String>> {
+        self.rendered_nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| RenderedNode {
                 index: node.index,
-                label,
+                label: self
+                    .renderer
+                    .display_label(node.index, &self.graph[node.index]),
                 x: node.x,
                 y: node.y,
                 width: node.width,
                 height: node.height,
-                style: self.node_styles.get(idx).cloned().unwrap_or_default(),
-            };
-            self.renderer.render_node(&mut grid, &node_with_label);
-        }
+                style: if self.selected == Some(node.index) {
+                    self.selection_style.node.clone()
+                } else {
+                    self.node_styles.get(idx).cloned().unwrap_or_default()
+                },
+            })
+            .collect()
+    }
+
+    /// Laid out nodes with their display labels and current styles.
+    pub fn layout_nodes(&mut self) -> Vec<RenderedNode<String>> {
+        self.update_layout();
+        self.labeled_nodes()
+    }
+
+    /// Laid out edges with their routed paths, labels and current styles.
+    pub fn layout_edges(&mut self) -> Vec<RenderedEdge<String>> {
+        self.update_layout();
+        self.labeled_edges()
+    }
 
+    /// Render the graph to a character grid.
+    pub fn render_to_grid(&mut self) -> CharGrid {
+        self.update_layout();
+
+        let (mut width, mut height) = self.grid_size();
+        let legend = self.legend_lines();
+        if !legend.is_empty() {
+            let legend_width = legend.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+            width = width.max(self.renderer.padding + legend_width);
+            height += legend.len();
+        }
+        let mut grid = CharGrid::new(width, height);
+        self.render_to(&mut grid);
         grid
     }
 
+    /// Render the graph onto any [`Canvas`].
+    ///
+    /// The legend, if any, is drawn below the graph.
+    pub fn render_to<C: Canvas>(&mut self, canvas: &mut C) {
+        self.update_layout();
+
+        // Render edges first (so nodes draw on top)
+        for edge in self.labeled_edges() {
+            self.renderer
+                .render_edge(canvas, &edge, &self.rendered_nodes);
+        }
+
+        // Render nodes
+        for node in self.labeled_nodes() {
+            self.renderer.render_node(canvas, &node);
+        }
+
+        let (_, top) = self.grid_size();
+        for (i, line) in self.legend_lines().iter().enumerate() {
+            canvas.draw_text(self.renderer.padding, top + i, line, Color::Gray);
+        }
+    }
+
+    /// Render the graph to an SVG document with the default [`SvgRenderer`].
+    pub fn render_to_svg(&mut self) -> String {
+        self.render_to_svg_with(&SvgRenderer::default())
+    }
+
+    /// Render the graph to an SVG document with a custom [`SvgRenderer`].
+    ///
+    /// Node and edge colors as well as the border style are kept, so the
+    /// picture matches what the terminal widget shows.
+    pub fn render_to_svg_with(&mut self, svg: &SvgRenderer) -> String {
+        self.update_layout();
+
+        let (width, height) = self.grid_size();
+        svg.render(&self.labeled_nodes(), &self.labeled_edges(), width, height)
+    }
+
     /// Create a widget for rendering with ratatui.
     pub fn widget(&mut self) -> GraphWidget<'_, N, E> {
-        GraphWidget { graph: self }
+        GraphWidget {
+            graph: self,
+            settle_indicator: false,
+        }
     }
 }
 
@@ -510,36 +918,48 @@ impl<N, E> Default for RenderedGraphBuilder<N, E> {
 }
 
 /// Ratatui widget for rendering the graph.
+///
+/// Shows the part of the graph selected by the graph's [`Viewport`].
 pub struct GraphWidget<'a, N, E> {
     graph: &'a mut RenderedGraph<N, E>,
+    settle_indicator: bool,
+}
+
+impl<N, E> GraphWidget<'_, N, E> {
+    /// Show whether the layout is still settling in the top-right corner.
+    ///
+    /// Useful when the host calls [`RenderedGraph::tick`] every frame.
+    pub fn settle_indicator(mut self, show: bool) -> Self {
+        self.settle_indicator = show;
+        self
+    }
 }
 
 impl<N: Display + Clone, E: Display + Clone> Widget for GraphWidget<'_, N, E> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let grid = self.graph.render_to_grid();
-        let (grid_width, grid_height) = grid.size();
-
-        for (gx, gy, cell) in grid.iter() {
-            // Map grid coordinates to buffer coordinates
-            let bx = area.x + gx as u16;
-            let by = area.y + gy as u16;
-
-            // Check bounds
-            if bx >= area.x + area.width || by >= area.y + area.height {
-                continue;
-            }
-            if gx >= grid_width || gy >= grid_height {
-                continue;
-            }
-
-            let buf_cell = buf.cell_mut((bx, by));
-            if let Some(buf_cell) = buf_cell {
-                buf_cell.set_char(cell.char);
-                buf_cell.set_fg(cell.fg);
-                if cell.bg != Color::Reset {
-                    buf_cell.set_bg(cell.bg);
-                }
-            }
+        self.graph.viewport.render(&grid, area, buf);
+
+        if self.settle_indicator {
+            let (text, color) = if self.graph.is_stable() {
+                ("settled".to_string(), Color::Green)
+            } else {
+                const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+                let iterations = self.graph.iterations();
+                let spinner = SPINNER[iterations % SPINNER.len()];
+                (
+                    format!("{} settling {}", spinner, iterations),
+                    Color::Yellow,
+                )
+            };
+            let width = (text.len() as u16).min(area.width);
+            buf.set_stringn(
+                area.right() - width,
+                area.y,
+                &text,
+                width as usize,
+                Style::new().fg(color),
+            );
         }
     }
 }
@@ -646,6 +1066,55 @@ mod tests {
         assert_eq!(rendered.physics_config().spring_constant, 0.2);
     }
 
+    #[test]
+    fn test_render_to_canvas() {
+        use crate::core::asciibox::ab_grid::AsciiboxGrid;
+
+        let mut graph: DiGraph<&str, &str> = DiGraph::new();
+        let a = graph.add_node("Start");
+        let b = graph.add_node("End");
+        graph.add_edge(a, b, "go");
+
+        let mut rendered = RenderedGraph::builder()
+            .graph(graph)
+            .border_style(BoxBorder::Ascii)
+            .build();
+        rendered.run_simulation();
+
+        let chars = rendered.render_to_grid().to_string();
+        let mut grid = AsciiboxGrid::new(0, 0, ' ');
+        rendered.render_to(&mut grid);
+        // AsciiboxGrid trims trailing blanks and empty trailing rows
+        let mut expected: Vec<&str> = chars.lines().map(|l| l.trim_end()).collect();
+        while expected.last().is_some_and(|l| l.is_empty()) {
+            expected.pop();
+        }
+        let actual = grid.to_string();
+        assert_eq!(actual, format!("{}\n", expected.join("\n")));
+        assert!(actual.contains("| Start |"));
+    }
+
+    #[test]
+    fn test_render_to_svg() {
+        let mut graph: DiGraph<&str, &str> = DiGraph::new();
+        let a = graph.add_node("Start");
+        let b = graph.add_node("End");
+        let e = graph.add_edge(a, b, "go");
+
+        let mut rendered = RenderedGraph::from_graph(graph);
+        rendered.run_simulation();
+        rendered.set_node_border_color(a, Color::Rgb(0x12, 0x34, 0x56));
+        rendered.set_edge_color(e, Color::Red);
+
+        let svg = rendered.render_to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">Start</text>"));
+        assert!(svg.contains(">End</text>"));
+        assert!(svg.contains(">go</text>"));
+        assert!(svg.contains("stroke=\"#123456\""));
+        assert!(svg.contains("<polygon"), "missing arrowhead:\n{}", svg);
+    }
+
     #[test]
     fn test_scaling_mode() {
         let mut graph: DiGraph<&str, &str> = DiGraph::new();
@@ -671,4 +1140,212 @@ mod tests {
         let (w3, _) = grid3.size();
         assert!(w3 <= w1);
     }
+
+    #[test]
+    fn test_selection() {
+        let mut graph: DiGraph<&str, &str> = DiGraph::new();
+        let a = graph.add_node("A");
+        let b = graph.add_node("B");
+        let c = graph.add_node("C");
+        graph.add_edge(a, b, "");
+
+        let mut rendered = RenderedGraph::builder()
+            .graph(graph)
+            .border_style(BoxBorder::Ascii)
+            .build();
+        rendered.run_simulation();
+        assert!(!rendered.render_to_grid().to_string().contains('═'));
+
+        // Any key selects the first node, Next/Prev wrap around
+        assert!(rendered.move_selection(NavKey::Down));
+        assert_eq!(rendered.selected(), Some(a));
+        rendered.move_selection(NavKey::Prev);
+        assert_eq!(rendered.selected(), Some(c));
+        rendered.move_selection(NavKey::Next);
+        rendered.select(Some(a));
+        rendered.select(Some(NodeIndex::new(9)));
+        assert_eq!(
+            rendered.take_selection_events(),
+            vec![
+                SelectionEvent::Selected(a),
+                SelectionEvent::Selected(c),
+                SelectionEvent::Selected(a),
+                SelectionEvent::Cleared,
+            ]
+        );
+        assert!(rendered.take_selection_events().is_empty());
+
+        // Arrow keys follow the edge and come back with the opposite key
+        rendered.select(Some(a));
+        let pairs = [
+            (NavKey::Left, NavKey::Right),
+            (NavKey::Right, NavKey::Left),
+            (NavKey::Up, NavKey::Down),
+            (NavKey::Down, NavKey::Up),
+        ];
+        let (_, back) = pairs
+            .into_iter()
+            .find(|&(key, _)| {
+                rendered.select(Some(a));
+                rendered.move_selection(key) && rendered.selected() == Some(b)
+            })
+            .expect("no key leads from A to B");
+        rendered.move_selection(back);
+        assert_eq!(rendered.selected(), Some(a));
+
+        // Highlight does not touch the node's own style
+        let output = rendered.render_to_grid().to_string();
+        assert!(output.contains("║ A ║"), "{}", output);
+        assert!(output.contains("| B |"), "{}", output);
+        assert_eq!(rendered.node_style(a).unwrap().border, BoxBorder::Ascii);
+
+        rendered.activate_selection();
+        rendered.clear_selection();
+        let events = rendered.take_selection_events();
+        assert_eq!(
+            events[events.len() - 2..],
+            [SelectionEvent::Activated(a), SelectionEvent::Cleared]
+        );
+        assert!(!rendered.render_to_grid().to_string().contains('║'));
+    }
+
+    #[test]
+    fn test_viewport() {
+        let mut graph: DiGraph<String, &str> = DiGraph::new();
+        let nodes: Vec<_> = (0..8)
+            .map(|i| graph.add_node(format!("Node{}", i)))
+            .collect();
+        for pair in nodes.windows(2) {
+            graph.add_edge(pair[0], pair[1], "");
+        }
+        let mut rendered = RenderedGraph::from_graph(graph);
+        rendered.run_simulation();
+        let (grid_width, grid_height) = rendered.render_to_grid().size();
+        assert!(grid_width > 20 && grid_height > 8);
+
+        let area = Rect::new(0, 0, 20, 8);
+        let render = |rendered: &mut RenderedGraph<String, &str>| {
+            let mut buf = Buffer::empty(area);
+            rendered.widget().render(area, &mut buf);
+            buf
+        };
+        let first = render(&mut rendered);
+        assert_eq!(rendered.viewport().width, 20);
+
+        // Panning past the end stops at the last column
+        rendered.pan(10_000, 0);
+        let panned = render(&mut rendered);
+        assert_eq!(rendered.viewport().x, grid_width - 20);
+        assert_ne!(first, panned);
+
+        let last = *nodes.last().unwrap();
+        rendered.center_on_node(last);
+        rendered.reveal_node(last);
+        let node = &rendered.layout_nodes()[last.index()];
+        let view = *rendered.viewport();
+        assert!(node.x >= view.x && node.x + node.width <= view.x + view.width);
+
+        // Minimap sits in the bottom-right corner
+        rendered.set_minimap(true);
+        let buf = render(&mut rendered);
+        assert_eq!(buf[(19, 7)].symbol(), "┘");
+        assert_ne!(first[(19, 7)].symbol(), "┘");
+    }
+
+    #[test]
+    fn test_animation_and_drag() {
+        let mut graph: DiGraph<&str, &str> = DiGraph::new();
+        let a = graph.add_node("A");
+        let b = graph.add_node("B");
+        let c = graph.add_node("C");
+        graph.add_edge(a, b, "");
+        graph.add_edge(b, c, "");
+
+        let mut rendered = RenderedGraph::from_graph(graph);
+        let area = Rect::new(0, 0, 60, 20);
+        let mut buf = Buffer::empty(area);
+        rendered
+            .widget()
+            .settle_indicator(true)
+            .render(area, &mut buf);
+        let top: String = (0..60).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(top.contains("settling 0"), "{}", top);
+
+        // Intermediate states stay on the grid
+        assert!(rendered.animate(5));
+        assert_eq!(rendered.iterations(), 5);
+        assert!(rendered.layout_nodes().iter().any(|n| n.x == 2));
+        while rendered.animate(50) {}
+        let mut buf = Buffer::empty(area);
+        rendered
+            .widget()
+            .settle_indicator(true)
+            .render(area, &mut buf);
+        let top: String = (0..60).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(top.contains("settled"), "{}", top);
+
+        // Dragging pins the node and the layout settles again
+        let position = |r: &mut RenderedGraph<&str, &str>| {
+            r.layout_nodes()
+                .iter()
+                .map(|n| (n.x, n.y))
+                .collect::<Vec<_>>()
+        };
+        let before = position(&mut rendered);
+        rendered.drag_node(c, 20, 0);
+        assert!(rendered.is_node_pinned(c));
+        assert!(!rendered.is_stable());
+        rendered.run_simulation();
+        assert!(rendered.is_stable());
+        assert_ne!(position(&mut rendered), before);
+
+        rendered.set_node_pinned(c, false);
+        assert!(!rendered.is_node_pinned(c));
+    }
+
+    #[test]
+    fn test_auto_scale_and_legend() {
+        use super::super::render::ScalingMode;
+
+        let mut graph: DiGraph<&str, &str> = DiGraph::new();
+        let a = graph.add_node("parse_node_list");
+        let b = graph.add_node("render_edge_labels");
+        let c = graph.add_node("Short");
+        graph.add_edge(a, b, "");
+        graph.add_edge(b, c, "");
+
+        let mut rendered = RenderedGraph::from_graph(graph);
+        rendered.run_simulation();
+        let (width, height) = rendered.render_to_grid().size();
+
+        // Enough room keeps the full labels
+        rendered.auto_scale_to(width, height);
+        assert_eq!(rendered.renderer.scaling_mode, ScalingMode::Full);
+        assert!(rendered.legend().is_empty());
+
+        // Too low: squeeze before touching labels
+        rendered.auto_scale_to(width, height - 4);
+        assert_eq!(rendered.renderer.scaling_mode, ScalingMode::Full);
+        assert!(rendered.renderer.zoom < 1.0);
+        assert!(rendered.layout_size().1 <= height - 4);
+
+        // Nothing fits: numeric IDs with a legend
+        rendered.auto_scale_to(4, 4);
+        assert_eq!(rendered.renderer.scaling_mode, ScalingMode::NumericIds);
+        let output = rendered.render_to_grid().to_string();
+        assert!(output.contains("Legend:"), "{}", output);
+        assert!(output.contains("0  parse_node_list"), "{}", output);
+        assert!(output.contains("2  Short"), "{}", output);
+
+        rendered.set_scaling_mode(ScalingMode::Acronym);
+        assert_eq!(
+            rendered.legend(),
+            vec![
+                ("PNL".to_string(), "parse_node_list".to_string()),
+                ("REL".to_string(), "render_edge_labels".to_string()),
+            ]
+        );
+        rendered.set_show_legend(false);
+        assert!(!rendered.render_to_grid().to_string().contains("Legend:"));
+    }
 }
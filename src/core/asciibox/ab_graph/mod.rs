@@ -1,12 +1,20 @@
+pub mod canvas;
 pub mod physics;
 pub mod render;
 pub mod style;
+pub mod svg;
 
 mod ext;
 mod graph;
+mod selection;
 mod test;
+mod viewport;
 
+pub use canvas::Canvas;
 pub use ext::AsciiGraphExt;
 pub use graph::{RenderedGraph, RenderedGraphBuilder};
 pub use render::ScalingMode;
+pub use selection::{NavKey, SelectionEvent, SelectionStyle};
 pub use style::{BoxBorder, EdgeStyle, NodeStyle};
+pub use svg::SvgRenderer;
+pub use viewport::Viewport;
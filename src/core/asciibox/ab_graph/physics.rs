@@ -110,6 +110,8 @@ impl std::ops::Mul<f64> for Vec2 {
 pub struct NodePhysics {
     pub position: Vec2,
     pub velocity: Vec2,
+    /// Pinned nodes keep their position, e.g. while being dragged.
+    pub pinned: bool,
 }
 
 impl NodePhysics {
@@ -117,6 +119,7 @@ impl NodePhysics {
         Self {
             position: Vec2::new(x, y),
             velocity: Vec2::default(),
+            pinned: false,
         }
     }
 }
@@ -195,6 +198,10 @@ impl PhysicsEngine {
 
         // Apply forces and update positions
         for (i, node) in self.nodes.iter_mut().enumerate() {
+            if node.pinned {
+                node.velocity = Vec2::default();
+                continue;
+            }
             node.velocity = (node.velocity + forces[i] * self.config.dt) * self.config.damping;
             node.position += node.velocity * self.config.dt;
         }
@@ -228,6 +235,28 @@ impl PhysicsEngine {
         self.iteration
     }
 
+    /// Restart the iteration count so that the layout settles again.
+    pub fn reheat(&mut self) {
+        self.iteration = 0;
+    }
+
+    /// Pin or unpin a node.
+    pub fn set_pinned(&mut self, node: NodeIndex, pinned: bool) {
+        self.nodes[node.index()].pinned = pinned;
+    }
+
+    /// Check if a node is pinned.
+    pub fn is_pinned(&self, node: NodeIndex) -> bool {
+        self.nodes[node.index()].pinned
+    }
+
+    /// Move a node by an offset in physics units.
+    pub fn move_by(&mut self, node: NodeIndex, delta: Vec2) {
+        let node = &mut self.nodes[node.index()];
+        node.position += delta;
+        node.velocity = Vec2::default();
+    }
+
     /// Get position of a node by index.
     pub fn position(&self, node: NodeIndex) -> Vec2 {
         self.nodes[node.index()].position
@@ -276,4 +305,23 @@ mod tests {
 
         assert!(engine.is_stable());
     }
+
+    #[test]
+    fn test_pinned_node() {
+        let mut graph: DiGraph<&str, &str> = DiGraph::new();
+        let a = graph.add_node("A");
+        let b = graph.add_node("B");
+        graph.add_edge(a, b, "edge");
+
+        let mut engine = PhysicsEngine::new(&graph, PhysicsConfig::default());
+        engine.set_pinned(a, true);
+        engine.move_by(a, Vec2::new(500.0, 0.0));
+        engine.run(&graph);
+        assert!((engine.position(a).x - 500.0).abs() < 0.001);
+        assert!(engine.position(b).x > 100.0);
+
+        engine.reheat();
+        assert_eq!(engine.iterations(), 0);
+        assert!(!engine.is_stable());
+    }
 }
@@ -2,10 +2,19 @@
 
 use std::fmt::Display;
 
+use super::canvas::Canvas;
 use super::style::{EdgeChars, EdgeStyle, NodeStyle};
 use petgraph::graph::{EdgeIndex, NodeIndex};
 use ratatui::style::Color;
 
+/// Direction an edge leaves its source node in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EdgeRoute {
+    Down,
+    Up,
+    Horizontal,
+}
+
 /// A character cell in the render grid.
 #[derive(Debug, Clone)]
 pub struct Cell {
@@ -53,19 +62,6 @@ impl CharGrid {
         }
     }
 
-    /// Set a character at (x, y) with a color.
-    pub fn set_char(&mut self, x: usize, y: usize, c: char, fg: Color) {
-        self.set(
-            x,
-            y,
-            Cell {
-                char: c,
-                fg,
-                bg: Color::Reset,
-            },
-        );
-    }
-
     /// Get a cell at (x, y).
     pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
         if x < self.width && y < self.height {
@@ -84,27 +80,6 @@ impl CharGrid {
         }
     }
 
-    /// Draw a horizontal line.
-    pub fn draw_hline(&mut self, x: usize, y: usize, len: usize, c: char, fg: Color) {
-        for i in 0..len {
-            self.set_char(x + i, y, c, fg);
-        }
-    }
-
-    /// Draw a vertical line.
-    pub fn draw_vline(&mut self, x: usize, y: usize, len: usize, c: char, fg: Color) {
-        for i in 0..len {
-            self.set_char(x, y + i, c, fg);
-        }
-    }
-
-    /// Draw text at position.
-    pub fn draw_text(&mut self, x: usize, y: usize, text: &str, fg: Color) {
-        for (i, c) in text.chars().enumerate() {
-            self.set_char(x + i, y, c, fg);
-        }
-    }
-
     /// Iterate over all cells with coordinates.
     pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &Cell)> {
         self.cells.iter().enumerate().map(move |(i, cell)| {
@@ -142,6 +117,20 @@ impl CharGrid {
     }
 }
 
+impl Canvas for CharGrid {
+    fn set_char(&mut self, x: usize, y: usize, c: char, fg: Color) {
+        self.set(
+            x,
+            y,
+            Cell {
+                char: c,
+                fg,
+                bg: Color::Reset,
+            },
+        );
+    }
+}
+
 /// Rendered node with position and size information.
 #[derive(Debug, Clone)]
 pub struct RenderedNode<N> {
@@ -198,10 +187,67 @@ pub enum ScalingMode {
     Full,
     /// Truncate long labels.
     Truncate(usize),
+    /// Keep the start and end of long labels with an ellipsis in the middle.
+    Abbreviate(usize),
+    /// Use the initials of the words in a label, e.g. `PNL` for `parse_node_list`.
+    Acronym,
     /// Use numeric IDs instead of labels.
     NumericIds,
 }
 
+impl ScalingMode {
+    /// Whether labels lose so much information that a legend is needed.
+    pub fn needs_legend(&self) -> bool {
+        matches!(self, ScalingMode::Acronym | ScalingMode::NumericIds)
+    }
+}
+
+/// Shorten `label` to `max_len` characters with an ellipsis in the middle.
+pub fn abbreviate(label: &str, max_len: usize) -> String {
+    let chars: Vec<char> = label.chars().collect();
+    if chars.len() <= max_len || max_len < 3 {
+        return label.to_string();
+    }
+    // Keep one more character at the start, it usually tells the most
+    let tail = (max_len - 1) / 2;
+    let head = max_len - 1 - tail;
+    let mut s: String = chars[..head].iter().collect();
+    s.push('…');
+    s.extend(&chars[chars.len() - tail..]);
+    s
+}
+
+/// Initials of the words in `label`, in upper case.
+///
+/// Words are separated by spaces, `_`, `-`, `.` or a lower to upper case
+/// change. Labels with a single word are kept.
+pub fn acronym(label: &str) -> String {
+    let mut initials = String::new();
+    let mut words = 0;
+    let mut prev: Option<char> = None;
+    for c in label.chars() {
+        let separator = c.is_whitespace() || matches!(c, '_' | '-' | '.');
+        let starts_word = !separator
+            && match prev {
+                None => true,
+                Some(p) => {
+                    p.is_whitespace()
+                        || matches!(p, '_' | '-' | '.')
+                        || (p.is_lowercase() && c.is_uppercase())
+                }
+            };
+        if starts_word {
+            initials.extend(c.to_uppercase());
+            words += 1;
+        }
+        prev = Some(c);
+    }
+    if words < 2 {
+        return label.to_string();
+    }
+    initials
+}
+
 /// Graph renderer that converts physics positions to character grid.
 pub struct GraphRenderer {
     /// Scaling factor from physics units to characters.
@@ -217,6 +263,8 @@ pub struct GraphRenderer {
     pub edge_chars: EdgeChars,
     /// Current scaling mode.
     pub scaling_mode: ScalingMode,
+    /// Extra factor applied to both scales, used to squeeze the layout.
+    pub zoom: f64,
 }
 
 impl Default for GraphRenderer {
@@ -229,6 +277,7 @@ impl Default for GraphRenderer {
             min_spacing: 3,
             edge_chars: EdgeChars::default(),
             scaling_mode: ScalingMode::Full,
+            zoom: 1.0,
         }
     }
 }
@@ -240,79 +289,150 @@ impl GraphRenderer {
             ScalingMode::Full => label.to_string(),
             ScalingMode::Truncate(max_len) => {
                 let s = label.to_string();
-                if s.len() > max_len {
-                    format!("{}…", &s[..max_len - 1])
+                if s.chars().count() > max_len {
+                    let head: String = s.chars().take(max_len.saturating_sub(1)).collect();
+                    format!("{}…", head)
                 } else {
                     s
                 }
             }
+            ScalingMode::Abbreviate(max_len) => abbreviate(&label.to_string(), max_len),
+            ScalingMode::Acronym => acronym(&label.to_string()),
             ScalingMode::NumericIds => index.index().to_string(),
         }
     }
 
+    /// Position in the character grid of a physics coordinate.
+    pub fn to_grid(&self, x: f64, y: f64) -> (usize, usize) {
+        (
+            (x * self.scale_x * self.zoom) as usize + self.padding,
+            (y * self.scale_y * self.zoom) as usize + self.padding,
+        )
+    }
+
     /// Calculate node width based on label.
     pub fn node_width(&self, label: &str) -> usize {
         // Border + padding + text + padding + border
         label.len() + 4
     }
 
+    /// Compute the routed path of an edge as a list of corner points.
+    ///
+    /// The first point is the cell just outside the source node and the last
+    /// point is the cell holding the arrowhead. Intermediate points are the
+    /// corners of an L-shaped route. [`GraphRenderer::render_edge`] draws
+    /// along this path, so other backends get the same shape.
+    pub fn route_edge<E, N>(
+        &self,
+        edge: &RenderedEdge<E>,
+        nodes: &[RenderedNode<N>],
+    ) -> Vec<(usize, usize)> {
+        self.route(edge, nodes)
+            .map(|(_, path)| path)
+            .unwrap_or_default()
+    }
+
+    /// Route an edge, returning the direction it leaves the source in and its path.
+    fn route<E, N>(
+        &self,
+        edge: &RenderedEdge<E>,
+        nodes: &[RenderedNode<N>],
+    ) -> Option<(EdgeRoute, Vec<(usize, usize)>)> {
+        let offset = edge.parallel_offset;
+
+        // Find source and target nodes
+        let source = nodes.iter().find(|n| n.index == edge.source)?;
+        let target = nodes.iter().find(|n| n.index == edge.target)?;
+
+        // Apply offset for parallel edges
+        let apply_offset = |x: usize| -> usize {
+            if offset >= 0 {
+                x.saturating_add(offset as usize)
+            } else {
+                x.saturating_sub((-offset) as usize)
+            }
+        };
+
+        // Calculate connection points - determine if we go down or up
+        let (route, start_x, start_y, end_x, end_y) = if source.bottom_y() < target.top_y() {
+            // Source is above target - go down
+            (
+                EdgeRoute::Down,
+                apply_offset(source.center_x()),
+                source.bottom_y() + 1,
+                apply_offset(target.center_x()),
+                target.top_y().saturating_sub(1),
+            )
+        } else if target.bottom_y() < source.top_y() {
+            // Target is above source - go up (back edge)
+            (
+                EdgeRoute::Up,
+                apply_offset(source.center_x()),
+                source.top_y().saturating_sub(1),
+                apply_offset(target.center_x()),
+                target.bottom_y() + 1,
+            )
+        } else {
+            // Nodes overlap vertically - draw horizontally, parallel edges
+            // are spread symmetrically around the center row
+            let (start_x, end_x) = if source.x + source.width < target.x {
+                (source.x + source.width, target.x.saturating_sub(1))
+            } else {
+                (source.x.saturating_sub(1), target.x + target.width)
+            };
+            let y = apply_offset(source.y + source.height / 2);
+            return Some((EdgeRoute::Horizontal, vec![(start_x, y), (end_x, y)]));
+        };
+
+        if start_x == end_x {
+            return Some((route, vec![(start_x, start_y), (end_x, end_y)]));
+        }
+        // L-shaped routing through the middle row
+        let (y_min, y_max) = if start_y < end_y {
+            (start_y, end_y)
+        } else {
+            (end_y, start_y)
+        };
+        let mid_y = ((start_y + end_y) / 2).max(y_min).min(y_max);
+        Some((
+            route,
+            vec![
+                (start_x, start_y),
+                (start_x, mid_y),
+                (end_x, mid_y),
+                (end_x, end_y),
+            ],
+        ))
+    }
+
     /// Render a node box to the grid.
-    pub fn render_node<N: Display>(&self, grid: &mut CharGrid, node: &RenderedNode<N>) {
+    ///
+    /// The label is drawn as is, see [`GraphRenderer::display_label`].
+    pub fn render_node<C: Canvas, N: Display>(&self, grid: &mut C, node: &RenderedNode<N>) {
         let chars = node.style.border.chars();
-        let label = self.display_label(node.index, &node.label);
+        let label = node.label.to_string();
         let width = node.width;
 
-        // Top border
-        grid.set_char(node.x, node.y, chars.top_left, node.style.border_color);
-        grid.draw_hline(
-            node.x + 1,
+        grid.draw_box(
+            node.x,
             node.y,
-            width - 2,
-            chars.horizontal,
-            node.style.border_color,
-        );
-        grid.set_char(
-            node.x + width - 1,
-            node.y,
-            chars.top_right,
+            width,
+            node.height,
+            &chars,
             node.style.border_color,
         );
 
         // Middle row with label
         let mid_y = node.y + 1;
-        grid.set_char(node.x, mid_y, chars.vertical, node.style.border_color);
         grid.set_char(node.x + 1, mid_y, ' ', node.style.text_color);
         grid.draw_text(node.x + 2, mid_y, &label, node.style.text_color);
         grid.set_char(node.x + width - 2, mid_y, ' ', node.style.text_color);
-        grid.set_char(
-            node.x + width - 1,
-            mid_y,
-            chars.vertical,
-            node.style.border_color,
-        );
-
-        // Bottom border
-        let bot_y = node.y + 2;
-        grid.set_char(node.x, bot_y, chars.bottom_left, node.style.border_color);
-        grid.draw_hline(
-            node.x + 1,
-            bot_y,
-            width - 2,
-            chars.horizontal,
-            node.style.border_color,
-        );
-        grid.set_char(
-            node.x + width - 1,
-            bot_y,
-            chars.bottom_right,
-            node.style.border_color,
-        );
     }
 
     /// Render an edge to the grid.
-    pub fn render_edge<E: Display, N>(
+    pub fn render_edge<C: Canvas, E: Display, N>(
         &self,
-        grid: &mut CharGrid,
+        grid: &mut C,
         edge: &RenderedEdge<E>,
         nodes: &[RenderedNode<N>],
     ) {
@@ -321,84 +441,25 @@ impl GraphRenderer {
         let label = edge.label.to_string();
         let offset = edge.parallel_offset;
 
-        // Find source and target nodes
-        let source = nodes.iter().find(|n| n.index == edge.source);
-        let target = nodes.iter().find(|n| n.index == edge.target);
-
-        let (source, target) = match (source, target) {
-            (Some(s), Some(t)) => (s, t),
-            _ => return,
+        let (route, path) = match self.route(edge, nodes) {
+            Some(routed) => routed,
+            None => return,
         };
 
-        // Apply horizontal offset for parallel edges
-        let apply_offset = |x: usize| -> usize {
-            if offset >= 0 {
-                x.saturating_add(offset as usize)
-            } else {
-                x.saturating_sub((-offset) as usize)
-            }
-        };
-
-        // Calculate connection points - determine if we go down or up
-        let source_bottom = source.bottom_y();
-        let source_top = source.top_y();
-        let target_bottom = target.bottom_y();
-        let target_top = target.top_y();
-
-        if source_bottom < target_top {
-            // Source is above target - go down
-            let start_x = apply_offset(source.center_x());
-            let start_y = source_bottom + 1;
-            let end_x = apply_offset(target.center_x());
-            let end_y = target_top.saturating_sub(1);
-
+        if route != EdgeRoute::Horizontal {
             self.render_vertical_edge(
                 grid,
                 chars,
                 color,
-                start_x,
-                start_y,
-                end_x,
-                end_y,
-                true,
-                &label,
-                edge.style.text_color,
-                offset,
-            );
-        } else if target_bottom < source_top {
-            // Target is above source - go up (back edge)
-            let start_x = apply_offset(source.center_x());
-            let start_y = source_top.saturating_sub(1);
-            let end_x = apply_offset(target.center_x());
-            let end_y = target_bottom + 1;
-
-            self.render_vertical_edge(
-                grid,
-                chars,
-                color,
-                start_x,
-                start_y,
-                end_x,
-                end_y,
-                false,
+                &path,
+                route == EdgeRoute::Down,
                 &label,
                 edge.style.text_color,
                 offset,
             );
         } else {
-            // Nodes overlap vertically - draw horizontally
-            let (start_x, end_x) = if source.x + source.width < target.x {
-                (source.x + source.width, target.x.saturating_sub(1))
-            } else {
-                (source.x.saturating_sub(1), target.x + target.width)
-            };
-            // Apply vertical offset for parallel horizontal edges (symmetric around center)
-            let base_y = source.y + source.height / 2;
-            let y = if offset >= 0 {
-                base_y.saturating_add(offset as usize)
-            } else {
-                base_y.saturating_sub((-offset) as usize)
-            };
+            let (start_x, y) = path[0];
+            let (end_x, _) = path[path.len() - 1];
 
             // Simple horizontal edge
             let (left, right) = if start_x < end_x {
@@ -439,21 +500,20 @@ impl GraphRenderer {
 
     /// Helper to render vertical or L-shaped edges
     #[allow(clippy::too_many_arguments)]
-    fn render_vertical_edge(
+    fn render_vertical_edge<C: Canvas>(
         &self,
-        grid: &mut CharGrid,
+        grid: &mut C,
         chars: &EdgeChars,
         color: Color,
-        start_x: usize,
-        start_y: usize,
-        end_x: usize,
-        end_y: usize,
+        path: &[(usize, usize)],
         going_down: bool,
         label: &str,
         label_color: Color,
         parallel_offset: i32,
     ) {
-        if start_x == end_x {
+        let (start_x, start_y) = path[0];
+        let (end_x, end_y) = path[path.len() - 1];
+        if path.len() == 2 {
             // Straight vertical line
             let (y_start, y_end) = if start_y < end_y {
                 (start_y, end_y)
@@ -483,14 +543,8 @@ impl GraphRenderer {
                 }
             }
         } else {
-            // L-shaped routing
-            let mid_y = (start_y + end_y) / 2;
-            let (y_min, y_max) = if start_y < end_y {
-                (start_y, end_y)
-            } else {
-                (end_y, start_y)
-            };
-            let mid_y = mid_y.max(y_min).min(y_max);
+            // L-shaped routing, the second point is the corner at the source column
+            let mid_y = path[1].1;
 
             // Vertical from source to mid
             let (v1_start, v1_end) = if start_y < mid_y {
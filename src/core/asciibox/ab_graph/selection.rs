@@ -0,0 +1,101 @@
+//! Selection model for interactive graph views.
+
+use petgraph::graph::NodeIndex;
+use ratatui::style::Color;
+
+use super::style::{BoxBorder, EdgeStyle, NodeStyle};
+
+/// Navigation keys understood by [`RenderedGraph::move_selection`].
+///
+/// The host maps its own key events (crossterm, GTK, ...) to these.
+///
+/// [`RenderedGraph::move_selection`]: super::RenderedGraph::move_selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Next node in index order (Tab).
+    Next,
+    /// Previous node in index order (Shift+Tab).
+    Prev,
+}
+
+impl NavKey {
+    /// Unit vector of the key in grid coordinates, `None` for Next/Prev.
+    fn direction(self) -> Option<(isize, isize)> {
+        match self {
+            NavKey::Up => Some((0, -1)),
+            NavKey::Down => Some((0, 1)),
+            NavKey::Left => Some((-1, 0)),
+            NavKey::Right => Some((1, 0)),
+            NavKey::Next | NavKey::Prev => None,
+        }
+    }
+
+    /// Score of a move by (dx, dy) in the direction of this key, lower is
+    /// better. Returns `None` when the target does not lie in that direction.
+    pub fn score(self, dx: isize, dy: isize) -> Option<isize> {
+        let (ux, uy) = self.direction()?;
+        // Terminal cells are about twice as tall as they are wide
+        let (dx, dy) = (dx, dy * 2);
+        let along = dx * ux + dy * uy;
+        if along <= 0 {
+            return None;
+        }
+        let across = (dx * uy - dy * ux).abs();
+        Some(along + 2 * across)
+    }
+}
+
+/// Selection changes reported to the host application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionEvent {
+    /// A node became selected.
+    Selected(NodeIndex),
+    /// The selection was cleared.
+    Cleared,
+    /// The selected node was activated (e.g. Enter was pressed).
+    Activated(NodeIndex),
+}
+
+/// Styles used to highlight the selected node and its incident edges.
+#[derive(Debug, Clone)]
+pub struct SelectionStyle {
+    /// Style of the selected node.
+    pub node: NodeStyle,
+    /// Style of edges entering or leaving the selected node.
+    pub edge: EdgeStyle,
+}
+
+impl Default for SelectionStyle {
+    fn default() -> Self {
+        // Double border keeps the selection visible without colors
+        Self {
+            node: NodeStyle {
+                border: BoxBorder::Double,
+                border_color: Color::Yellow,
+                text_color: Color::Yellow,
+            },
+            edge: EdgeStyle {
+                line_color: Color::Yellow,
+                text_color: Color::Yellow,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        assert_eq!(NavKey::Right.score(-3, 0), None);
+        assert_eq!(NavKey::Right.score(0, 2), None);
+        assert!(NavKey::Right.score(4, 0) < NavKey::Right.score(4, 1));
+        assert!(NavKey::Down.score(0, 3) < NavKey::Down.score(6, 3));
+        assert_eq!(NavKey::Next.score(1, 1), None);
+    }
+}
@@ -3,7 +3,7 @@
 use ratatui::style::Color;
 
 /// Box border style for nodes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum BoxBorder {
     /// Single line borders: ┌─┐│└─┘
     #[default]
@@ -12,11 +12,25 @@ pub enum BoxBorder {
     Double,
     /// Rounded corners: ╭─╮│╰─╯
     Rounded,
+    /// Heavy line borders: ┏━┓┃┗━┛
+    Heavy,
     /// ASCII only: +-+|+-+
     Ascii,
 }
 
 impl BoxBorder {
+    /// Parse a border style name such as `single` or `rounded`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "single" => Some(BoxBorder::Single),
+            "double" => Some(BoxBorder::Double),
+            "rounded" | "round" => Some(BoxBorder::Rounded),
+            "heavy" | "bold" => Some(BoxBorder::Heavy),
+            "ascii" => Some(BoxBorder::Ascii),
+            _ => None,
+        }
+    }
+
     /// Get the corner and edge characters for this border style.
     /// Returns (top_left, top_right, bottom_left, bottom_right, horizontal, vertical)
     pub fn chars(self) -> BorderChars {
@@ -45,6 +59,14 @@ impl BoxBorder {
                 horizontal: '─',
                 vertical: '│',
             },
+            BoxBorder::Heavy => BorderChars {
+                top_left: '┏',
+                top_right: '┓',
+                bottom_left: '┗',
+                bottom_right: '┛',
+                horizontal: '━',
+                vertical: '┃',
+            },
             BoxBorder::Ascii => BorderChars {
                 top_left: '+',
                 top_right: '+',
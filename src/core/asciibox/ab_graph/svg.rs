@@ -0,0 +1,414 @@
+//! Native SVG rendering for graphs.
+//!
+//! Unlike going through svgbob, this backend draws nodes, edges, arrowheads
+//! and labels as vector primitives and keeps the colors and border styles
+//! from [`NodeStyle`](super::style::NodeStyle) and
+//! [`EdgeStyle`](super::style::EdgeStyle).
+
+use std::fmt::Write;
+
+use ratatui::style::Color;
+
+use super::render::{RenderedEdge, RenderedNode};
+use super::style::BoxBorder;
+
+/// Configuration for the SVG backend.
+///
+/// Positions are taken from the character grid layout, so one grid cell maps
+/// to a `cell_width` x `cell_height` rectangle in the output.
+#[derive(Debug, Clone)]
+pub struct SvgRenderer {
+    /// Width of one grid cell in pixels.
+    pub cell_width: f64,
+    /// Height of one grid cell in pixels.
+    pub cell_height: f64,
+    /// Font family used for labels.
+    pub font_family: String,
+    /// Font size used for labels.
+    pub font_size: f64,
+    /// Stroke width for borders and edges.
+    pub stroke_width: f64,
+    /// Background fill, `Color::Reset` for a transparent background.
+    pub background: Color,
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self {
+            cell_width: 8.0,
+            cell_height: 16.0,
+            font_family: "monospace".to_string(),
+            font_size: 14.0,
+            stroke_width: 1.5,
+            background: Color::Black,
+        }
+    }
+}
+
+impl SvgRenderer {
+    /// Render nodes and routed edges into an SVG document.
+    ///
+    /// `width` and `height` are the size of the character grid.
+    pub fn render(
+        &self,
+        nodes: &[RenderedNode<String>],
+        edges: &[RenderedEdge<String>],
+        width: usize,
+        height: usize,
+    ) -> String {
+        let px_width = width as f64 * self.cell_width;
+        let px_height = height as f64 * self.cell_height;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            fmt_num(px_width),
+            fmt_num(px_height),
+            fmt_num(px_width),
+            fmt_num(px_height)
+        );
+        if let Some(bg) = css_color(self.background) {
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="0" y="0" width="100%" height="100%" fill="{}"/>"#,
+                bg
+            );
+        }
+        let _ = writeln!(
+            svg,
+            r#"  <g font-family="{}" font-size="{}">"#,
+            escape_xml(&self.font_family),
+            fmt_num(self.font_size)
+        );
+
+        // Edges first so that node boxes are drawn on top
+        for edge in edges {
+            self.render_edge(&mut svg, edge, nodes);
+        }
+        for node in nodes {
+            self.render_node(&mut svg, node);
+        }
+
+        svg.push_str("  </g>\n</svg>\n");
+        svg
+    }
+
+    /// Center of a grid cell in pixels.
+    fn cell_center(&self, x: usize, y: usize) -> (f64, f64) {
+        (
+            (x as f64 + 0.5) * self.cell_width,
+            (y as f64 + 0.5) * self.cell_height,
+        )
+    }
+
+    fn render_node(&self, svg: &mut String, node: &RenderedNode<String>) {
+        let stroke = css_color(node.style.border_color).unwrap_or("currentColor".to_string());
+        let fill = css_color(node.style.text_color).unwrap_or("currentColor".to_string());
+
+        // The border characters sit in the middle of the outer cells
+        let (x0, y0) = self.cell_center(node.x, node.y);
+        let w = node.width.saturating_sub(1) as f64 * self.cell_width;
+        let h = node.height.saturating_sub(1) as f64 * self.cell_height;
+
+        let radius = match node.style.border {
+            BoxBorder::Rounded => self.cell_width.min(self.cell_height) * 0.8,
+            _ => 0.0,
+        };
+        // Heavy borders are drawn with a thicker stroke
+        let stroke_width = match node.style.border {
+            BoxBorder::Heavy => self.stroke_width * 2.0,
+            _ => self.stroke_width,
+        };
+        self.push_rect(svg, x0, y0, w, h, radius, stroke_width, &stroke);
+        if node.style.border == BoxBorder::Double {
+            let inset = self.stroke_width * 2.0;
+            self.push_rect(
+                svg,
+                x0 + inset,
+                y0 + inset,
+                w - inset * 2.0,
+                h - inset * 2.0,
+                0.0,
+                stroke_width,
+                &stroke,
+            );
+        }
+
+        let cx = x0 + w / 2.0;
+        let cy = y0 + h / 2.0;
+        let _ = writeln!(
+            svg,
+            r#"    <text x="{}" y="{}" fill="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            fmt_num(cx),
+            fmt_num(cy),
+            fill,
+            escape_xml(&node.label)
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push_rect(
+        &self,
+        svg: &mut String,
+        x: f64,
+        y: f64,
+        w: f64,
+        h: f64,
+        r: f64,
+        stroke_width: f64,
+        stroke: &str,
+    ) {
+        let _ = writeln!(
+            svg,
+            r#"    <rect x="{}" y="{}" width="{}" height="{}" rx="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            fmt_num(x),
+            fmt_num(y),
+            fmt_num(w.max(0.0)),
+            fmt_num(h.max(0.0)),
+            fmt_num(r),
+            stroke,
+            fmt_num(stroke_width)
+        );
+    }
+
+    fn render_edge(
+        &self,
+        svg: &mut String,
+        edge: &RenderedEdge<String>,
+        nodes: &[RenderedNode<String>],
+    ) {
+        if edge.path.len() < 2 {
+            return;
+        }
+        let stroke = css_color(edge.style.line_color).unwrap_or("currentColor".to_string());
+        let text_fill = css_color(edge.style.text_color).unwrap_or("currentColor".to_string());
+
+        let mut points: Vec<(f64, f64)> = edge
+            .path
+            .iter()
+            .map(|&(x, y)| self.cell_center(x, y))
+            .collect();
+
+        // Stretch both ends so the line touches the node borders
+        let source = nodes.iter().find(|n| n.index == edge.source);
+        let target = nodes.iter().find(|n| n.index == edge.target);
+        if let Some(source) = source {
+            let next = points[1];
+            self.snap_to_border(&mut points[0], next, source);
+        }
+        if let Some(target) = target {
+            let last = points.len() - 1;
+            let prev = points[last - 1];
+            self.snap_to_border(&mut points[last], prev, target);
+        }
+
+        let mut data = String::new();
+        for (i, (x, y)) in points.iter().enumerate() {
+            let cmd = if i == 0 { 'M' } else { 'L' };
+            let _ = write!(data, "{}{} {} ", cmd, fmt_num(*x), fmt_num(*y));
+        }
+        let _ = writeln!(
+            svg,
+            r#"    <path d="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            data.trim_end(),
+            stroke,
+            fmt_num(self.stroke_width)
+        );
+
+        // Arrowhead pointing along the last segment
+        let (tip_x, tip_y) = points[points.len() - 1];
+        let (from_x, from_y) = points[points.len() - 2];
+        let (dx, dy) = (tip_x - from_x, tip_y - from_y);
+        let len = (dx * dx + dy * dy).sqrt();
+        if len > 0.0 {
+            let (ux, uy) = (dx / len, dy / len);
+            let size = self.cell_width.min(self.cell_height) * 0.8;
+            let (bx, by) = (tip_x - ux * size, tip_y - uy * size);
+            let (px, py) = (-uy * size * 0.5, ux * size * 0.5);
+            let _ = writeln!(
+                svg,
+                r#"    <polygon points="{},{} {},{} {},{}" fill="{}"/>"#,
+                fmt_num(tip_x),
+                fmt_num(tip_y),
+                fmt_num(bx + px),
+                fmt_num(by + py),
+                fmt_num(bx - px),
+                fmt_num(by - py),
+                stroke
+            );
+        }
+
+        if !edge.label.is_empty() {
+            self.render_edge_label(svg, edge, &points, &text_fill);
+        }
+    }
+
+    /// Move an end point of an edge onto the border of its node.
+    fn snap_to_border(
+        &self,
+        point: &mut (f64, f64),
+        towards: (f64, f64),
+        node: &RenderedNode<String>,
+    ) {
+        let (left, top) = self.cell_center(node.x, node.y);
+        let (right, bottom) = self.cell_center(
+            node.x + node.width.saturating_sub(1),
+            node.y + node.height.saturating_sub(1),
+        );
+        if (point.0 - towards.0).abs() < f64::EPSILON {
+            // Vertical segment
+            point.1 = point.1.clamp(top, bottom);
+        } else {
+            point.0 = point.0.clamp(left, right);
+        }
+    }
+
+    /// Place the label next to the longest segment of the edge.
+    fn render_edge_label(
+        &self,
+        svg: &mut String,
+        edge: &RenderedEdge<String>,
+        points: &[(f64, f64)],
+        fill: &str,
+    ) {
+        let mut best = (0, 0.0);
+        for i in 0..points.len() - 1 {
+            let (a, b) = (points[i], points[i + 1]);
+            let len = (a.0 - b.0).abs() + (a.1 - b.1).abs();
+            if len > best.1 {
+                best = (i, len);
+            }
+        }
+        let (a, b) = (points[best.0], points[best.0 + 1]);
+        let (mx, my) = ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+        let vertical = (a.0 - b.0).abs() < f64::EPSILON;
+
+        let (x, y, anchor) = if vertical {
+            if edge.parallel_offset < 0 {
+                (mx - self.cell_width, my, "end")
+            } else {
+                (mx + self.cell_width, my, "start")
+            }
+        } else if edge.parallel_offset <= 0 {
+            (mx, my - self.cell_height * 0.6, "middle")
+        } else {
+            (mx, my + self.cell_height * 0.6, "middle")
+        };
+        let _ = writeln!(
+            svg,
+            r#"    <text x="{}" y="{}" fill="{}" text-anchor="{}" dominant-baseline="central">{}</text>"#,
+            fmt_num(x),
+            fmt_num(y),
+            fill,
+            anchor,
+            escape_xml(&edge.label)
+        );
+    }
+}
+
+/// Convert a terminal color into a CSS color, `None` for `Color::Reset`.
+pub fn css_color(color: Color) -> Option<String> {
+    let rgb = match color {
+        Color::Reset => return None,
+        Color::Black => (0x00, 0x00, 0x00),
+        Color::Red => (0xcd, 0x00, 0x00),
+        Color::Green => (0x00, 0xcd, 0x00),
+        Color::Yellow => (0xcd, 0xcd, 0x00),
+        Color::Blue => (0x00, 0x00, 0xee),
+        Color::Magenta => (0xcd, 0x00, 0xcd),
+        Color::Cyan => (0x00, 0xcd, 0xcd),
+        Color::Gray => (0xe5, 0xe5, 0xe5),
+        Color::DarkGray => (0x7f, 0x7f, 0x7f),
+        Color::LightRed => (0xff, 0x00, 0x00),
+        Color::LightGreen => (0x00, 0xff, 0x00),
+        Color::LightYellow => (0xff, 0xff, 0x00),
+        Color::LightBlue => (0x5c, 0x5c, 0xff),
+        Color::LightMagenta => (0xff, 0x00, 0xff),
+        Color::LightCyan => (0x00, 0xff, 0xff),
+        Color::White => (0xff, 0xff, 0xff),
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i) => indexed_rgb(i),
+    };
+    Some(format!("#{:02x}{:02x}{:02x}", rgb.0, rgb.1, rgb.2))
+}
+
+/// RGB value of a color in the xterm 256 color palette.
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    const BASE: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00),
+        (0xcd, 0x00, 0x00),
+        (0x00, 0xcd, 0x00),
+        (0xcd, 0xcd, 0x00),
+        (0x00, 0x00, 0xee),
+        (0xcd, 0x00, 0xcd),
+        (0x00, 0xcd, 0xcd),
+        (0xe5, 0xe5, 0xe5),
+        (0x7f, 0x7f, 0x7f),
+        (0xff, 0x00, 0x00),
+        (0x00, 0xff, 0x00),
+        (0xff, 0xff, 0x00),
+        (0x5c, 0x5c, 0xff),
+        (0xff, 0x00, 0xff),
+        (0x00, 0xff, 0xff),
+        (0xff, 0xff, 0xff),
+    ];
+    match i {
+        0..=15 => BASE[i as usize],
+        16..=231 => {
+            let i = i - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            (level(i / 36), level((i / 6) % 6), level(i % 6))
+        }
+        _ => {
+            let v = 8 + (i - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+/// Escape text for use in XML content and attributes.
+pub fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Format a number without a trailing `.0` for integral values.
+fn fmt_num(v: f64) -> String {
+    if v.fract() == 0.0 {
+        format!("{}", v as i64)
+    } else {
+        format!("{:.2}", v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_color() {
+        assert_eq!(css_color(Color::Reset), None);
+        assert_eq!(
+            css_color(Color::Rgb(1, 2, 255)),
+            Some("#0102ff".to_string())
+        );
+        assert_eq!(css_color(Color::Indexed(196)), Some("#ff0000".to_string()));
+        assert_eq!(css_color(Color::Indexed(232)), Some("#080808".to_string()));
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("a<b & \"c\""), "a&lt;b &amp; &quot;c&quot;");
+    }
+}
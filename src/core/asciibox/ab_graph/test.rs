@@ -366,6 +366,30 @@ fn test_border_style_rounded() {
     );
 }
 
+#[test]
+fn test_border_style_heavy() {
+    let mut graph: DiGraph<&str, &str> = DiGraph::new();
+    graph.add_node("X");
+
+    let mut rendered = RenderedGraph::builder()
+        .graph(graph)
+        .border_style(BoxBorder::Heavy)
+        .build();
+    rendered.run_simulation();
+    let output = render_to_string(&mut rendered);
+
+    assert!(
+        output.contains('┏'),
+        "Missing heavy top-left corner:\n{}",
+        output
+    );
+    assert!(
+        output.contains('┛'),
+        "Missing heavy bottom-right corner:\n{}",
+        output
+    );
+}
+
 #[test]
 fn test_border_style_ascii() {
     let mut graph: DiGraph<&str, &str> = DiGraph::new();
@@ -403,6 +427,12 @@ fn test_scaling_mode_numeric() {
     rendered.run_simulation();
     rendered.set_scaling_mode(ScalingMode::NumericIds);
 
+    // Full labels only appear in the legend below the graph
+    let output = render_to_string(&mut rendered);
+    assert!(output.contains("Legend:"), "{}", output);
+    assert!(output.contains("0  VeryLongNodeLabel"), "{}", output);
+
+    rendered.set_show_legend(false);
     let output = render_to_string(&mut rendered);
 
     // Should have numeric IDs instead of labels
@@ -429,3 +459,19 @@ fn test_scaling_mode_truncate() {
     // Should NOT have the full label
     assert!(!output.contains("VeryLongNodeLabel"));
 }
+
+#[test]
+fn test_scaling_mode_abbreviate() {
+    use crate::core::asciibox::ab_graph::render::{abbreviate, acronym};
+
+    assert_eq!(abbreviate("VeryLongNodeLabel", 8), "Very…bel");
+    assert_eq!(abbreviate("Short", 8), "Short");
+    assert_eq!(abbreviate("很长很长的节点名字", 5), "很长…名字");
+
+    assert_eq!(acronym("parse_node_list"), "PNL");
+    assert_eq!(acronym("VeryLongNodeLabel"), "VLNL");
+    assert_eq!(acronym("load dot-file"), "LDF");
+    // Single words and existing acronyms stay as they are
+    assert_eq!(acronym("VLNL"), "VLNL");
+    assert_eq!(acronym("start"), "start");
+}
@@ -0,0 +1,175 @@
+//! Scrollable view onto a rendered graph that may be larger than the terminal.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Widget};
+
+use super::render::CharGrid;
+
+/// The part of the full [`CharGrid`] that the widget shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Viewport {
+    /// Grid column shown at the left edge of the widget.
+    pub x: usize,
+    /// Grid row shown at the top edge of the widget.
+    pub y: usize,
+    /// Width of the area the widget last rendered into.
+    pub width: usize,
+    /// Height of the area the widget last rendered into.
+    pub height: usize,
+    /// Show a minimap overlay when the grid does not fit in the view.
+    pub minimap: bool,
+}
+
+impl Viewport {
+    /// Move the view by (dx, dy) cells, stopping at the top-left corner.
+    pub fn scroll_by(&mut self, dx: isize, dy: isize) {
+        self.x = self.x.saturating_add_signed(dx);
+        self.y = self.y.saturating_add_signed(dy);
+    }
+
+    /// Move the top-left corner of the view to (x, y).
+    pub fn scroll_to(&mut self, x: usize, y: usize) {
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Put the grid position (x, y) in the middle of the view.
+    pub fn center_on(&mut self, x: usize, y: usize) {
+        self.x = x.saturating_sub(self.width / 2);
+        self.y = y.saturating_sub(self.height / 2);
+    }
+
+    /// Scroll as little as possible so that the given rectangle is visible.
+    pub fn reveal(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        if x + width > self.x + self.width {
+            self.x = (x + width).saturating_sub(self.width);
+        }
+        if y + height > self.y + self.height {
+            self.y = (y + height).saturating_sub(self.height);
+        }
+        self.x = self.x.min(x);
+        self.y = self.y.min(y);
+    }
+
+    /// Keep the view inside a grid of the given size.
+    pub fn clamp(&mut self, grid_width: usize, grid_height: usize) {
+        self.x = self.x.min(grid_width.saturating_sub(self.width));
+        self.y = self.y.min(grid_height.saturating_sub(self.height));
+    }
+
+    /// Whether the whole grid fits in the view.
+    pub fn fits(&self, grid_width: usize, grid_height: usize) -> bool {
+        grid_width <= self.width && grid_height <= self.height
+    }
+
+    /// Copy the visible part of `grid` into `area`, then draw the minimap.
+    ///
+    /// Updates the view size to `area` and clamps the offsets first.
+    pub fn render(&mut self, grid: &CharGrid, area: Rect, buf: &mut Buffer) {
+        self.width = area.width as usize;
+        self.height = area.height as usize;
+        let (grid_width, grid_height) = grid.size();
+        self.clamp(grid_width, grid_height);
+
+        for by in 0..area.height {
+            for bx in 0..area.width {
+                let Some(cell) = grid.get(self.x + bx as usize, self.y + by as usize) else {
+                    continue;
+                };
+                if let Some(buf_cell) = buf.cell_mut((area.x + bx, area.y + by)) {
+                    buf_cell.set_char(cell.char);
+                    buf_cell.set_fg(cell.fg);
+                    if cell.bg != Color::Reset {
+                        buf_cell.set_bg(cell.bg);
+                    }
+                }
+            }
+        }
+
+        if self.minimap && !self.fits(grid_width, grid_height) {
+            self.render_minimap(grid, area, buf);
+        }
+    }
+
+    /// Draw a scaled down copy of the grid in the bottom-right corner, with
+    /// the visible part highlighted.
+    fn render_minimap(&self, grid: &CharGrid, area: Rect, buf: &mut Buffer) {
+        let (grid_width, grid_height) = grid.size();
+        // Same scale on both axes, at most a quarter of the width and half the height
+        let max_width = (area.width as usize / 4).saturating_sub(2);
+        let max_height = (area.height as usize / 2).saturating_sub(2);
+        if max_width == 0 || max_height == 0 {
+            return;
+        }
+        let scale = grid_width
+            .div_ceil(max_width)
+            .max(grid_height.div_ceil(max_height))
+            .max(1);
+        let width = grid_width.div_ceil(scale);
+        let height = grid_height.div_ceil(scale);
+
+        let outer = Rect::new(
+            area.right() - width as u16 - 2,
+            area.bottom() - height as u16 - 2,
+            width as u16 + 2,
+            height as u16 + 2,
+        );
+        let block = Block::bordered().border_style(Style::new().fg(Color::DarkGray));
+        let inner = block.inner(outer);
+        block.render(outer, buf);
+
+        for my in 0..height {
+            for mx in 0..width {
+                let (gx, gy) = (mx * scale, my * scale);
+                let filled = (gy..(gy + scale).min(grid_height)).any(|y| {
+                    (gx..(gx + scale).min(grid_width))
+                        .any(|x| grid.get(x, y).is_some_and(|c| c.char != ' '))
+                });
+                let visible = gx + scale > self.x
+                    && gx < self.x + self.width
+                    && gy + scale > self.y
+                    && gy < self.y + self.height;
+                if let Some(cell) = buf.cell_mut((inner.x + mx as u16, inner.y + my as u16)) {
+                    cell.set_char(if filled { '·' } else { ' ' });
+                    cell.set_fg(Color::Gray);
+                    cell.set_bg(if visible {
+                        Color::DarkGray
+                    } else {
+                        Color::Reset
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll() {
+        let mut view = Viewport {
+            width: 10,
+            height: 5,
+            ..Default::default()
+        };
+        view.scroll_by(-3, 4);
+        assert_eq!((view.x, view.y), (0, 4));
+        view.center_on(30, 2);
+        assert_eq!((view.x, view.y), (25, 0));
+        view.clamp(28, 4);
+        assert_eq!((view.x, view.y), (18, 0));
+
+        // Only scrolls when the target is hidden
+        view.reveal(20, 1, 4, 3);
+        assert_eq!((view.x, view.y), (18, 0));
+        view.reveal(2, 6, 4, 3);
+        assert_eq!((view.x, view.y), (2, 4));
+        view.reveal(30, 0, 4, 3);
+        assert_eq!((view.x, view.y), (24, 0));
+        assert!(view.fits(10, 5) && !view.fits(11, 5));
+    }
+}
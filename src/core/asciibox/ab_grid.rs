@@ -1,117 +1,359 @@
-pub struct AsciiboxGrid {
+use ratatui::style::Color;
+
+use crate::{core::asciibox::ab_graph::Canvas, utils::cn_length};
+
+/// 宽字符（中文等）占两列，第二列用该字符占位，导出时跳过
+const WIDE_PAD: char = '\0';
+
+/// 绘制层，后面的层会覆盖前面的层
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    Edge,
+    Node,
+    Label,
+}
+
+impl Layer {
+    const ALL: [Layer; 3] = [Layer::Edge, Layer::Node, Layer::Label];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// 矩形区域，坐标可以为负
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridRect {
+    pub x: i32,
+    pub y: i32,
     pub width: usize,
     pub height: usize,
-    data: Vec<char>,
+}
+
+impl GridRect {
+    pub fn new(x: i32, y: i32, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && x < self.x + self.width as i32
+            && y < self.y + self.height as i32
+    }
+}
+
+/// 按需扩展的字符画布
+///
+/// 坐标可以为负，画布会自动扩展到绘制的位置。每个字符写入当前的 [`Layer`]，
+/// 导出时上层覆盖下层，并且去掉行尾空白。
+pub struct AsciiboxGrid {
+    // 画布左上角的坐标
+    origin_x: i32,
+    origin_y: i32,
+    width: usize,
+    height: usize,
+    // 背景字符
+    background: char,
+    // 每层的数据，None 表示透明
+    layers: Vec<Vec<Option<char>>>,
+    layer: Layer,
+    clip: Option<GridRect>,
 }
 
 impl AsciiboxGrid {
     pub fn new(width: usize, height: usize, ch: char) -> Self {
-        let mut data = Vec::new();
-        for _ in 0..=width * height {
-            data.push(ch);
-        }
         Self {
+            origin_x: 0,
+            origin_y: 0,
             width,
             height,
-            data,
+            background: ch,
+            layers: vec![vec![None; width * height]; Layer::ALL.len()],
+            layer: Layer::Node,
+            clip: None,
         }
     }
 
+    /// 清空所有层并设置新的背景字符
     pub fn fill(&mut self, ch: char) {
-        self.data.clear();
-        for _ in 0..=self.width * self.height {
-            self.data.push(ch);
+        self.background = ch;
+        for layer in self.layers.iter_mut() {
+            layer.fill(None);
         }
     }
 
-    pub fn set(&mut self, x: usize, y: usize, ch: char) {
-        self.data[x + y * self.width] = ch
+    /// 画布的左上角坐标
+    pub fn origin(&self) -> (i32, i32) {
+        (self.origin_x, self.origin_y)
     }
 
-    pub fn get(&self, x: usize, y: usize) -> char {
-        return self.data[x + y * self.width];
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
     }
 
-    pub fn row(&self, y: usize) -> &[char] {
-        let start = y * self.width;
-        let end = start + self.width;
-        &self.data[start..end]
+    pub fn layer(&self) -> Layer {
+        self.layer
     }
 
-    pub fn iter_rows(&self) -> impl Iterator<Item = &[char]> {
-        self.data.chunks(self.width)
+    pub fn set_layer(&mut self, layer: Layer) {
+        self.layer = layer;
     }
 
-    pub fn draw_line(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, ch: char) {
+    /// 在指定层上绘制，完成后恢复原来的层
+    pub fn with_layer<F: FnOnce(&mut Self)>(&mut self, layer: Layer, f: F) {
+        let old = self.layer;
+        self.layer = layer;
+        f(self);
+        self.layer = old;
+    }
+
+    pub fn clip(&self) -> Option<GridRect> {
+        self.clip
+    }
+
+    /// 设置裁剪区域，区域外的绘制会被忽略
+    pub fn set_clip(&mut self, clip: Option<GridRect>) {
+        self.clip = clip;
+    }
+
+    /// 在裁剪区域内绘制，完成后恢复原来的裁剪区域
+    pub fn with_clip<F: FnOnce(&mut Self)>(&mut self, clip: GridRect, f: F) {
+        let old = self.clip;
+        // 嵌套裁剪取交集
+        self.clip = Some(match old {
+            Some(o) => intersect(o, clip),
+            None => clip,
+        });
+        f(self);
+        self.clip = old;
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let (dx, dy) = (x - self.origin_x, y - self.origin_y);
+        if dx < 0 || dy < 0 || dx as usize >= self.width || dy as usize >= self.height {
+            return None;
+        }
+        Some(dx as usize + dy as usize * self.width)
+    }
+
+    /// 扩展画布，保证包含 (x0, y0) 到 (x1, y1) 的矩形
+    pub fn reserve(&mut self, x0: i32, y0: i32, x1: i32, y1: i32) {
+        let min_x = self.origin_x.min(x0.min(x1));
+        let min_y = self.origin_y.min(y0.min(y1));
+        let max_x = (self.origin_x + self.width as i32).max(x0.max(x1) + 1);
+        let max_y = (self.origin_y + self.height as i32).max(y0.max(y1) + 1);
+        let (width, height) = ((max_x - min_x) as usize, (max_y - min_y) as usize);
+        if min_x == self.origin_x
+            && min_y == self.origin_y
+            && width == self.width
+            && height == self.height
+        {
+            return;
+        }
+
+        let (ox, oy) = (
+            (self.origin_x - min_x) as usize,
+            (self.origin_y - min_y) as usize,
+        );
+        for layer in self.layers.iter_mut() {
+            let mut data = vec![None; width * height];
+            for y in 0..self.height {
+                let src = y * self.width;
+                let dst = (y + oy) * width + ox;
+                data[dst..dst + self.width].copy_from_slice(&layer[src..src + self.width]);
+            }
+            *layer = data;
+        }
+        self.origin_x = min_x;
+        self.origin_y = min_y;
+        self.width = width;
+        self.height = height;
+    }
+
+    /// 扩展画布以包含 rect 中没有被裁剪的部分，完全被裁剪时不扩展
+    fn reserve_visible(&mut self, rect: GridRect) {
+        let rect = match self.clip {
+            Some(clip) => intersect(rect, clip),
+            None => rect,
+        };
+        if rect.width > 0 && rect.height > 0 {
+            let (right, bottom) = (
+                rect.x + rect.width as i32 - 1,
+                rect.y + rect.height as i32 - 1,
+            );
+            self.reserve(rect.x, rect.y, right, bottom);
+        }
+    }
+
+    pub fn set(&mut self, x: i32, y: i32, ch: char) {
+        if self.clip.is_some_and(|clip| !clip.contains(x, y)) {
+            return;
+        }
+        if self.index(x, y).is_none() {
+            self.reserve(x, y, x, y);
+        }
+        let i = self.index(x, y).unwrap();
+        self.layers[self.layer.index()][i] = Some(ch);
+    }
+
+    /// 获取合成后的字符，越界时返回背景字符
+    pub fn get(&self, x: i32, y: i32) -> char {
+        let Some(i) = self.index(x, y) else {
+            return self.background;
+        };
+        for layer in Layer::ALL.iter().rev() {
+            if let Some(ch) = self.layers[layer.index()][i] {
+                return ch;
+            }
+        }
+        self.background
+    }
+
+    /// 获取指定层上的字符，None 表示透明
+    pub fn get_in(&self, layer: Layer, x: i32, y: i32) -> Option<char> {
+        self.index(x, y).and_then(|i| self.layers[layer.index()][i])
+    }
+
+    /// 合成后的一行，y 为画布坐标
+    pub fn row(&self, y: i32) -> Vec<char> {
+        (0..self.width as i32)
+            .map(|x| self.get(self.origin_x + x, y))
+            .collect()
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = Vec<char>> + '_ {
+        (0..self.height as i32).map(move |y| self.row(self.origin_y + y))
+    }
+
+    pub fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, ch: char) {
+        let (x, y) = (x1.min(x2), y1.min(y2));
+        let (width, height) = ((x1 - x2).unsigned_abs() + 1, (y1 - y2).unsigned_abs() + 1);
+        self.reserve_visible(GridRect::new(x, y, width as usize, height as usize));
         // Bresenham 算法
-        let dx = x2 as i32 - x1 as i32;
-        let dy = y2 as i32 - y1 as i32;
-        let steps = dx.abs().max(dy.abs());
+        let dx = x2 - x1;
+        let dy = y2 - y1;
+        let steps = dx.abs().max(dy.abs()).max(1);
 
         for i in 0..=steps {
             let x = x1 as f32 + (dx as f32 * i as f32 / steps as f32);
             let y = y1 as f32 + (dy as f32 * i as f32 / steps as f32);
-            self.set(x.round() as usize, y.round() as usize, ch);
+            self.set(x.round() as i32, y.round() as i32, ch);
         }
     }
 
-    pub fn draw_box(&mut self, x: usize, y: usize, width: usize, height: usize) {
-        // 顶边
-        for i in 0..width {
-            self.set(x + i, y, '-');
-        }
-        // 底边
-        for i in 0..width {
-            self.set(x + i, y + height - 1, '-');
-        }
-        // 左边
-        for i in 0..height {
-            self.set(x, y + i, '|');
-        }
-        // 右边
-        for i in 0..height {
-            self.set(x + width - 1, y + i, '|');
+    pub fn draw_box(&mut self, x: i32, y: i32, width: usize, height: usize) {
+        if width == 0 || height == 0 {
+            return;
         }
+        let (r, b) = (x + width as i32 - 1, y + height as i32 - 1);
+        self.reserve_visible(GridRect::new(x, y, width, height));
+        // 顶边和底边
+        self.draw_line(x, y, r, y, '-');
+        self.draw_line(x, b, r, b, '-');
+        // 左边和右边
+        self.draw_line(x, y, x, b, '|');
+        self.draw_line(r, y, r, b, '|');
         // 角落
         self.set(x, y, '+');
-        self.set(x + width - 1, y, '+');
-        self.set(x, y + height - 1, '+');
-        self.set(x + width - 1, y + height - 1, '+');
-    }
-
-    pub fn draw_box_with_name(
-        &mut self,
-        name: &String,
-        x: usize,
-        y: usize,
-        width: usize,
-        height: usize,
-    ) {
-        self.draw_box(x, y, width, height);
-        for (j, line) in name.split("\n").enumerate() {
-            let ty = y + j + 1;
-            for (i, ch) in line.chars().enumerate() {
-                // 从左开始，但保留一个空格
-                let tx = x + i + 2;
-                if tx < x + width - 1 && ty < y + height - 1 {
-                    self.set(tx, ty, ch);
+        self.set(r, y, '+');
+        self.set(x, b, '+');
+        self.set(r, b, '+');
+    }
+
+    /// 绘制文字，宽字符占两列，Canvas::draw_text 也使用这里的实现
+    fn put_text(&mut self, x: i32, y: i32, text: &str) {
+        // 先按整段文字扩展一次画布，裁剪掉的部分不扩展
+        let width = text
+            .chars()
+            .map(|ch| {
+                if cn_length(ch.encode_utf8(&mut [0; 4])) > 1 {
+                    2
+                } else {
+                    1
                 }
+            })
+            .sum();
+        self.reserve_visible(GridRect::new(x, y, width, 1));
+
+        let mut tx = x;
+        for ch in text.chars() {
+            if cn_length(ch.encode_utf8(&mut [0; 4])) > 1 {
+                // 宽字符只显示一半时整个跳过
+                if self.clip.is_none_or(|c| c.contains(tx + 1, y)) {
+                    self.set(tx, y, ch);
+                    self.set(tx + 1, y, WIDE_PAD);
+                }
+                tx += 2;
+                continue;
             }
+            self.set(tx, y, ch);
+            tx += 1;
         }
     }
 
+    pub fn draw_box_with_name(&mut self, name: &str, x: i32, y: i32, width: usize, height: usize) {
+        self.draw_box(x, y, width, height);
+        if width < 5 || height < 3 {
+            return;
+        }
+        // 文字只能出现在边框内部，两侧各保留一个空格
+        let inner = GridRect::new(x + 2, y + 1, width - 4, height - 2);
+        self.with_layer(Layer::Label, |grid| {
+            grid.with_clip(inner, |grid| {
+                for (j, line) in name.split('\n').enumerate() {
+                    grid.put_text(x + 2, y + 1 + j as i32, line);
+                }
+            });
+        });
+    }
+
+    /// 导出为字符串，去掉行尾空白以及末尾的空行
     pub fn to_string(&self) -> String {
+        let mut lines: Vec<String> = self
+            .iter_rows()
+            .map(|row| {
+                let line: String = row.into_iter().filter(|ch| *ch != WIDE_PAD).collect();
+                line.trim_end().to_string()
+            })
+            .collect();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
         let mut result = String::new();
-        for row in self.iter_rows() {
-            for ch in row {
-                result.push(*ch);
-            }
+        for line in lines {
+            result.push_str(&line);
             result.push('\n');
         }
         result
     }
 }
 
+impl Canvas for AsciiboxGrid {
+    // 纯文本画布，忽略颜色
+    fn set_char(&mut self, x: usize, y: usize, c: char, _fg: Color) {
+        self.set(x as i32, y as i32, c);
+    }
+
+    fn draw_text(&mut self, x: usize, y: usize, text: &str, _fg: Color) {
+        self.put_text(x as i32, y as i32, text);
+    }
+}
+
+fn intersect(a: GridRect, b: GridRect) -> GridRect {
+    let x = a.x.max(b.x);
+    let y = a.y.max(b.y);
+    let r = (a.x + a.width as i32).min(b.x + b.width as i32);
+    let bottom = (a.y + a.height as i32).min(b.y + b.height as i32);
+    GridRect::new(x, y, (r - x).max(0) as usize, (bottom - y).max(0) as usize)
+}
+
+#[cfg(test)]
 mod test {
 
     use super::*;
@@ -123,7 +365,7 @@ mod test {
         // 绘制流程图节点
         grid.draw_box(0, 0, 16, 3); // Start 框
         grid.draw_box(2, 4, 16, 3); // Start 框
-        grid.draw_box_with_name(&"aaaaaaaaaa\naaaa\na\naa".to_string(), 0, 8, 12, 4); // End 框
+        grid.draw_box_with_name("aaaaaaaaaa\naaaa\na\naa", 0, 8, 12, 4); // End 框
 
         // 绘制连接线
         //grid.draw_line(6, 4, 6, 6, '|');
@@ -132,9 +374,59 @@ mod test {
 
         let ascii = grid.to_string();
         println!("{}", ascii);
+        assert!(ascii.contains("| aaaaaaaa |"));
+        assert!(!ascii.contains("| a        |\n+"));
 
         // // 转换为 SVG
         // let svg = svgbob::to_svg(&ascii);
         // std::fs::write("output.svg", svg).unwrap();
     }
+
+    #[test]
+    fn test_auto_grow() {
+        let mut grid = AsciiboxGrid::new(0, 0, ' ');
+        grid.draw_box(-2, -1, 4, 3);
+        assert_eq!(grid.origin(), (-2, -1));
+        assert_eq!(grid.size(), (4, 3));
+        grid.set(5, 3, 'x');
+        assert_eq!(grid.size(), (8, 5));
+        assert_eq!(grid.get(-2, -1), '+');
+        assert_eq!(grid.get(100, 100), ' ');
+        assert_eq!(grid.to_string(), "+--+\n|  |\n+--+\n\n       x\n");
+        grid.draw_text(0, 5, "你好", Color::Reset);
+        assert_eq!(grid.size(), (8, 7));
+    }
+
+    #[test]
+    fn test_clip_before_reserve() {
+        let mut grid = AsciiboxGrid::new(4, 2, ' ');
+        grid.with_clip(GridRect::new(0, 0, 4, 2), |grid| {
+            grid.draw_line(-10, 0, 20, 0, '-');
+            grid.draw_box(2, -3, 10, 8);
+            grid.draw_line(0, 5, 3, 8, '\\');
+        });
+        assert_eq!(grid.origin(), (0, 0));
+        assert_eq!(grid.size(), (4, 2));
+        assert_eq!(grid.to_string(), "--|-\n  |\n");
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut grid = AsciiboxGrid::new(5, 1, ' ');
+        grid.with_layer(Layer::Edge, |g| g.draw_line(0, 0, 4, 0, '-'));
+        grid.with_layer(Layer::Label, |g| {
+            g.draw_text(1, 0, "ab", Color::Reset);
+        });
+        grid.set(3, 0, '|');
+        assert_eq!(grid.to_string(), "-ab|-\n");
+        assert_eq!(grid.get_in(Layer::Edge, 1, 0), Some('-'));
+        assert_eq!(grid.get_in(Layer::Node, 1, 0), None);
+    }
+
+    #[test]
+    fn test_clip_and_wide_chars() {
+        let mut grid = AsciiboxGrid::new(0, 0, ' ');
+        grid.draw_box_with_name("你好世界", 0, 0, 7, 3);
+        assert_eq!(grid.to_string(), "+-----+\n| 你  |\n+-----+\n");
+    }
 }
@@ -2,18 +2,19 @@ use petgraph::{graph::NodeIndex, stable_graph::StableDiGraph};
 use std::collections::HashMap;
 // use ascii_dag::Graph;
 
-use crate::{
-    core::asciibox::{
-        ab_cell::{ASharp, Direct},
-        ab_grid::AsciiboxGrid,
-        ab_parse::{parse_edge, parse_node},
-    },
-    utils::cn_length,
+use crate::core::asciibox::{
+    ab_cell::{ASharp, Direct},
+    ab_convert::unicode_to_svgbob,
+    ab_dot::{DotEdge, DotGraph, DotNode},
+    ab_graph::{AsciiGraphExt, BoxBorder, RenderedGraph},
+    ab_grid::AsciiboxGrid,
+    ab_parse::{parse_edge, parse_node},
 };
 
 #[derive(Clone, Default)]
 pub struct AsciiBoxMap {
-    pub graph: StableDiGraph<String, usize>,
+    // 节点权重为显示名称，边权重为箭头文字
+    pub graph: StableDiGraph<String, String>,
     pub node_index: usize,
     pub node_id_map: HashMap<String, usize>,
 }
@@ -43,7 +44,7 @@ impl AsciiBoxMap {
         let mut _sharp: ASharp;
 
         let mut direct: Direct;
-        let mut _a_text: String;
+        let mut a_text: String;
         let mut vtext: String;
         let mut src_node_id: usize;
         let mut dst_node_id: usize;
@@ -54,7 +55,7 @@ impl AsciiBoxMap {
                 break;
             }
             // edge
-            (direct, _a_text, vtext) = parse_edge(text.trim());
+            (direct, a_text, vtext) = parse_edge(text.trim());
             // node
             if vtext.len() <= 0 {
                 break;
@@ -64,42 +65,123 @@ impl AsciiBoxMap {
                 break;
             }
             dst_node_id = self.add_node(&id, &name);
-            // TODO: add arrow label
-            // match direct {
-            //     Direct::None => {}
-            //     Direct::Double => {
-            //         self.graph.add_edge(src_node_id, dst_node_id, None);
-            //         self.graph.add_edge(dst_node_id, src_node_id, None);
-            //     }
-            //     Direct::Left | Direct::LeftUp | Direct::LeftDown => {
-            //         self.graph.add_edge(dst_node_id, src_node_id, None);
-            //     }
-            //     Direct::Right | Direct::RightUp | Direct::RightDown => {
-            //         self.graph.add_edge(src_node_id, dst_node_id, None);
-            //     }
-            //     Direct::Up => {
-            //         self.graph.add_edge(dst_node_id, src_node_id, None);
-            //     }
-            //     Direct::Down => {
-            //         self.graph.add_edge(src_node_id, dst_node_id, None);
-            //     }
-            // }
+            self.add_edge(&direct, src_node_id, dst_node_id, &a_text);
             src_node_id = dst_node_id;
         }
         true
     }
 
     fn add_node(&mut self, id: &String, name: &String) -> usize {
+        let id = id.trim();
         if self.node_id_map.contains_key(id) {
             return *self.node_id_map.get(id).unwrap();
         }
         self.node_index += 1;
-        let node_id = self.node_index;
-        self.node_id_map.insert(name.clone(), node_id);
-        self.graph.add_node(id.to_string());
+        let node_id = self.graph.add_node(name.trim().to_string()).index();
+        self.node_id_map.insert(id.to_string(), node_id);
         return node_id;
     }
 
+    fn add_edge(&mut self, direct: &Direct, src: usize, dst: usize, text: &str) {
+        let (src, dst) = (NodeIndex::new(src), NodeIndex::new(dst));
+        match direct {
+            Direct::None => {}
+            Direct::Double => {
+                self.graph.add_edge(src, dst, text.to_string());
+                self.graph.add_edge(dst, src, text.to_string());
+            }
+            Direct::Left | Direct::LeftUp | Direct::LeftDown | Direct::Up => {
+                self.graph.add_edge(dst, src, text.to_string());
+            }
+            Direct::Right | Direct::RightUp | Direct::RightDown | Direct::Down => {
+                self.graph.add_edge(src, dst, text.to_string());
+            }
+        }
+    }
+
+    /// 从 DOT 文本中加载
+    pub fn load_dot(content: &str) -> Result<Self, String> {
+        let dot = DotGraph::parse(content)?;
+        // 流程图没有分组和样式，只保留节点、连线和文字
+        if !dot.clusters.is_empty() {
+            let names: Vec<&str> = dot.clusters.iter().map(|c| c.name.as_str()).collect();
+            log::warn!(
+                "dot clusters are not supported, dropped: {}",
+                names.join(", ")
+            );
+        }
+        let styled = dot
+            .nodes
+            .iter()
+            .filter(|n| {
+                n.border != BoxBorder::Single || n.color.is_some() || n.font_color.is_some()
+            })
+            .count()
+            + dot
+                .edges
+                .iter()
+                .filter(|e| e.color.is_some() || e.font_color.is_some())
+                .count();
+        if styled > 0 {
+            log::warn!(
+                "dot styles are not supported, dropped on {} nodes and edges",
+                styled
+            );
+        }
+        let mut map = AsciiBoxMap::default();
+        // add_node 会去掉 id 两侧的空白，连线按 DOT 中原始的 id 查找
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for node in dot.nodes.iter() {
+            index.insert(&node.id, map.add_node(&node.id, &node.label));
+        }
+        for edge in dot.edges.iter() {
+            let find = |id: &str| {
+                index
+                    .get(id)
+                    .copied()
+                    .ok_or(format!("unknown node \"{}\"", id))
+            };
+            let (src, dst) = (find(&edge.src)?, find(&edge.dst)?);
+            map.add_edge(&Direct::Right, src, dst, &edge.label);
+        }
+        Ok(map)
+    }
+
+    /// 转换为 DOT 文本
+    pub fn to_dot(&self) -> String {
+        let mut ids: Vec<(&String, &usize)> = self.node_id_map.iter().collect();
+        ids.sort_by_key(|(_, idx)| **idx);
+        let id_of: HashMap<usize, &String> = ids.iter().map(|(id, idx)| (**idx, *id)).collect();
+
+        let mut dot = DotGraph::default();
+        for (id, idx) in ids.iter() {
+            dot.nodes.push(DotNode {
+                id: id.to_string(),
+                label: self.graph[NodeIndex::new(**idx)].clone(),
+                border: BoxBorder::Single,
+                color: None,
+                font_color: None,
+                cluster: None,
+            });
+        }
+        for edge in self.graph.edge_indices() {
+            let (src, dst) = self.graph.edge_endpoints(edge).unwrap();
+            dot.edges.push(DotEdge {
+                src: id_of[&src.index()].to_string(),
+                dst: id_of[&dst.index()].to_string(),
+                label: self.graph[edge].clone(),
+                color: None,
+                font_color: None,
+            });
+        }
+        dot.to_dot()
+    }
+
+    /// 转换为可渲染的图
+    pub fn to_rendered(&self) -> RenderedGraph<String, String> {
+        (&self.graph).to_ascii()
+    }
+
     pub fn show(&self) {
         // println!("{}", self.graph.render());
     }
@@ -109,20 +191,12 @@ impl AsciiBoxMap {
     pub fn show_layout(&self) {}
 
     pub fn render_to_svgbob(&self) -> String {
-        // let ir = self.graph.compute_layout();
-        // let mut grid = AsciiboxGrid::new(256, 256);
-        // for node in ir.nodes() {
-        //     grid.draw_box_with_name(
-        //         &node.label.to_string(),
-        //         node.x,
-        //         node.y,
-        //         node.width,
-        //         node.height,
-        //     );
-        // }
-
-        // grid.to_string()
-        "".to_string()
+        let mut rendered = self.to_rendered();
+        rendered.run_simulation();
+        // 通过 Canvas 绘制，与终端显示的布局和连线一致，再转换为 svgbob 的 ascii 字符
+        let mut grid = AsciiboxGrid::new(0, 0, ' ');
+        rendered.render_to(&mut grid);
+        unicode_to_svgbob(&grid.to_string())
     }
 }
 
@@ -134,5 +208,48 @@ mod test {
         let b = AsciiBoxMap::load_content("a-->c\na-->b\na-->d\nb-->g1");
         b.show_layout();
         b.show();
+        assert_eq!(b.graph.node_count(), 5);
+        assert_eq!(b.graph.edge_count(), 4);
+    }
+
+    #[test]
+    fn test_dot() {
+        let b = AsciiBoxMap::load_content("a[Start] --|go|--> b[End]\nc <-- b");
+        let dot = b.to_dot();
+        assert!(dot.contains("\"a\" [label=\"Start\"];"), "{}", dot);
+        assert!(dot.contains("\"a\" -> \"b\" [label=\"go\"];"), "{}", dot);
+        assert!(dot.contains("\"b\" -> \"c\";"), "{}", dot);
+
+        let c = AsciiBoxMap::load_dot(&dot).unwrap();
+        assert_eq!(c.graph.node_count(), 3);
+        assert_eq!(c.graph.edge_count(), 2);
+        assert_eq!(c.to_dot(), dot);
+    }
+
+    #[test]
+    fn test_load_dot() {
+        let dot = r#"digraph {
+            subgraph cluster_a { a [label="Start\\nNow", color=red] }
+            a -> b
+            " c " -> a
+        }"#;
+        let map = AsciiBoxMap::load_dot(dot).unwrap();
+        // DOT 中的 \\n 是反斜杠加 n，不是换行
+        let a = petgraph::graph::NodeIndex::new(map.node_id_map["a"]);
+        assert_eq!(map.graph[a], "Start\\nNow");
+        assert_eq!(map.graph.edge_count(), 2);
+        assert!(map.node_id_map.contains_key("c"));
+    }
+
+    #[test]
+    fn test_render_to_svgbob() {
+        let b = AsciiBoxMap::load_content("a[Start] --|go|--> b[End]");
+        let ascii = b.render_to_svgbob();
+        assert!(ascii.is_ascii());
+        assert!(ascii.contains("| Start |"));
+        assert!(ascii.contains("| End |"));
+        assert!(ascii.contains("go"));
+        assert!(ascii.contains('v') || ascii.contains('>'));
+        assert!(svgbob::to_svg(&ascii).contains("<svg"));
     }
 }
@@ -1,8 +1,15 @@
 mod ab_cell;
-mod ab_graph;
+mod ab_convert;
+mod ab_dot;
+pub mod ab_graph;
 mod ab_grid;
 mod ab_map;
 mod ab_parse;
 mod test;
 
+pub use ab_cell::Direct;
+pub use ab_convert::{svgbob_to_unicode, unicode_to_svgbob};
+pub use ab_dot::{DotCluster, DotEdge, DotGraph, DotNode};
+pub use ab_graph::{BoxBorder, RenderedGraph};
+pub use ab_grid::{AsciiboxGrid, GridRect, Layer};
 pub use ab_map::AsciiBoxMap;
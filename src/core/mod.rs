@@ -1,9 +1,9 @@
-mod asciibox;
+pub mod asciibox;
 mod mermaid;
 mod settings;
-mod table;
+pub mod table;
 
-pub use asciibox::AsciiBoxMap;
+pub use asciibox::{AsciiBoxMap, BoxBorder, RenderedGraph, svgbob_to_unicode, unicode_to_svgbob};
 pub use mermaid::{MermaidTheme, MermaidThemeConfig, MermaidThemeManager};
 pub use settings::AppSettings;
-pub use table::{TableFormator, TableMode};
+pub use table::{SortMode, TableFormator, TableMode, TableOp};
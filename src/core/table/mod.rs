@@ -1,5 +1,24 @@
+mod table_asciidoc;
+mod table_box;
+mod table_cell;
+mod table_csv;
 mod table_data;
+mod table_edit;
 mod table_format;
+mod table_html;
+mod table_latex;
+mod table_org;
+mod table_rst;
+mod table_wiki;
+mod table_wrap;
 
-pub use table_data::TableData;
+pub use table_asciidoc::parse_asciidoc;
+pub use table_cell::{CellStyle, ColumnSpec, TableCell, VAlign};
+pub use table_csv::{detect_delimiter, parse_delimited};
+pub use table_data::{Align, TableData};
+pub use table_edit::{SortMode, TableOp};
 pub use table_format::{TableFormator, TableMode};
+pub use table_html::parse_html;
+pub use table_org::parse_org;
+pub use table_rst::{parse_rst_grid, parse_rst_simple};
+pub use table_wiki::{parse_jira, parse_mediawiki};
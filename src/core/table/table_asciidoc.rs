@@ -0,0 +1,419 @@
+use super::TableData;
+use super::table_cell::{CellStyle, ColumnSpec, TableCell, VAlign};
+use super::table_data::Align;
+
+/// 单元格说明符，写在 `|` 前面，例如 `2+|`、`.3+^|`、`3*a|`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CellSpec {
+    repeat: usize,
+    colspan: usize,
+    rowspan: usize,
+    halign: Option<Align>,
+    valign: Option<VAlign>,
+    style: CellStyle,
+}
+
+impl Default for CellSpec {
+    fn default() -> Self {
+        Self {
+            repeat: 1,
+            colspan: 1,
+            rowspan: 1,
+            halign: None,
+            valign: None,
+            style: CellStyle::default(),
+        }
+    }
+}
+
+impl CellSpec {
+    /// 解析说明符，格式不对时返回 None
+    fn parse(s: &str) -> Option<Self> {
+        let mut spec = CellSpec::default();
+        let mut rest = s;
+        // 重复 n*
+        if let Some((n, r)) = rest.split_once('*') {
+            spec.repeat = n.parse().ok().filter(|n| *n > 0)?;
+            rest = r;
+        }
+        // 合并 c.r+
+        if let Some((span, r)) = rest.split_once('+') {
+            let (c, r2) = span.split_once('.').unwrap_or((span, ""));
+            if c.is_empty() && r2.is_empty() {
+                return None;
+            }
+            if !c.is_empty() {
+                spec.colspan = c.parse().ok().filter(|n| *n > 0)?;
+            }
+            if !r2.is_empty() {
+                spec.rowspan = r2.parse().ok().filter(|n| *n > 0)?;
+            }
+            rest = r;
+        }
+        let mut chars = rest.chars().peekable();
+        if let Some(align) = chars.peek().and_then(|c| Align::from_asciidoc(*c)) {
+            spec.halign = Some(align);
+            chars.next();
+        }
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            spec.valign = Some(VAlign::from_asciidoc(chars.next()?)?);
+        }
+        if let Some(c) = chars.next() {
+            spec.style = CellStyle::from_asciidoc(c)?;
+        }
+        if chars.next().is_some() {
+            return None;
+        }
+        Some(spec)
+    }
+}
+
+/// 解析 cols 属性的值，例如 `<,2*^.>,>3m`
+pub fn parse_cols(value: &str) -> Vec<ColumnSpec> {
+    // cols=3 表示 3 个默认列
+    if let Ok(n) = value.trim().parse::<usize>() {
+        return vec![ColumnSpec::default(); n];
+    }
+    let mut columns = Vec::new();
+    for spec in value.split([',', ';']) {
+        let (n, spec) = match spec.split_once('*') {
+            Some((n, spec)) => (n.trim().parse::<usize>().unwrap_or(1), spec),
+            None => (1, spec),
+        };
+        columns.extend(std::iter::repeat_n(ColumnSpec::from_asciidoc(spec), n));
+    }
+    columns
+}
+
+/// 属性为 cols 时返回它的值，去掉两侧的引号
+pub fn cols_value(attr: &str) -> Option<&str> {
+    match attr.split_once('=') {
+        Some((key, value)) if key.trim() == "cols" => Some(value.trim().trim_matches('"')),
+        _ => None,
+    }
+}
+
+/// 拆分属性行，引号中的逗号不拆分，例如 `[cols="1,2",options="header"]`
+fn split_attributes(line: &str) -> Vec<String> {
+    let inner = line.trim();
+    let inner = inner.strip_prefix('[').unwrap_or(inner);
+    let inner = inner.strip_suffix(']').unwrap_or(inner);
+    let mut attributes = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in inner.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            ',' if !quoted => {
+                attributes.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        attributes.push(current.trim().to_string());
+    }
+    attributes
+}
+
+// options 中是否包含指定选项，支持 `options="header"`、`opts=header` 和 `%header`
+fn has_option(attributes: &[String], option: &str) -> bool {
+    attributes.iter().any(|attr| {
+        if let Some((key, value)) = attr.split_once('=')
+            && (key.trim() == "options" || key.trim() == "opts")
+        {
+            return value
+                .trim_matches('"')
+                .split(',')
+                .any(|v| v.trim() == option);
+        }
+        attr.split('%').skip(1).any(|v| v == option)
+    })
+}
+
+/// 单元格原文：说明符、内容和 `|` 所在的行
+struct RawCell {
+    spec: CellSpec,
+    text: String,
+    line: usize,
+}
+
+// 拆分出末尾的说明符，说明符前必须是空白或者行首
+fn split_trailing_spec(segment: &str) -> (&str, Option<CellSpec>) {
+    let start = segment
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(0);
+    let token = &segment[start..];
+    if token.is_empty() {
+        return (segment, None);
+    }
+    match CellSpec::parse(token) {
+        Some(spec) => (&segment[..start], Some(spec)),
+        None => (segment, None),
+    }
+}
+
+/// 按未转义的 `|` 拆分单元格
+fn split_cells(body: &str) -> Vec<RawCell> {
+    // 每个 | 的位置和所在行
+    let mut pipes: Vec<(usize, usize)> = Vec::new();
+    let mut line = 0;
+    let mut prev = '\0';
+    for (i, c) in body.char_indices() {
+        match c {
+            '\n' => line += 1,
+            '|' if prev != '\\' => pipes.push((i, line)),
+            _ => {}
+        }
+        prev = c;
+    }
+
+    let mut cells = Vec::new();
+    let Some(&(first, _)) = pipes.first() else {
+        return cells;
+    };
+    // 第一个单元格的说明符在行首
+    let mut spec = CellSpec::parse(body[..first].trim()).unwrap_or_default();
+    for (k, &(pos, line)) in pipes.iter().enumerate() {
+        let end = pipes.get(k + 1).map(|p| p.0).unwrap_or(body.len());
+        let segment = &body[pos + 1..end];
+        let (text, next_spec) = if k + 1 < pipes.len() {
+            split_trailing_spec(segment)
+        } else {
+            (segment, None)
+        };
+        cells.push(RawCell {
+            spec,
+            text: text.trim().to_string(),
+            line,
+        });
+        spec = next_spec.unwrap_or_default();
+    }
+    cells
+}
+
+/// 解析 asciidoc 表格，支持标题、属性行、合并单元格、单元格样式和多行单元格
+pub fn parse_asciidoc(text: &str, cell_max_w: usize, line_max_w: usize) -> Option<TableData> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut title = String::new();
+    let mut attributes: Vec<String> = Vec::new();
+
+    // 表格前的标题和属性行
+    let delimiters: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.trim().starts_with("|=="))
+        .map(|(i, _)| i)
+        .collect();
+    let (start, end) = match delimiters.as_slice() {
+        [start, end, ..] => (*start + 1, *end),
+        [start] => (*start + 1, lines.len()),
+        [] => (0, lines.len()),
+    };
+    let mut body_start = start;
+    for (i, line) in lines.iter().enumerate().take(start.max(1)) {
+        let line = line.trim();
+        if line.starts_with('.') && !line.starts_with("..") {
+            title = line.to_string();
+        } else if line.starts_with('[') && line.ends_with(']') {
+            attributes = split_attributes(line);
+        } else {
+            continue;
+        }
+        if start == 0 {
+            body_start = i + 1;
+        }
+    }
+    let body = lines[body_start..end].join("\n");
+
+    let cells = split_cells(&body);
+    if cells.is_empty() {
+        return None;
+    }
+
+    // 列数：优先使用 cols，否则使用第一行的单元格数
+    let columns = attributes
+        .iter()
+        .find_map(|attr| cols_value(attr))
+        .map(parse_cols)
+        .unwrap_or_default();
+    let first_line = cells[0].line;
+    let w = if columns.is_empty() {
+        cells
+            .iter()
+            .filter(|c| c.line == first_line)
+            .map(|c| c.spec.colspan * c.spec.repeat)
+            .sum()
+    } else {
+        columns.len()
+    };
+    if w == 0 {
+        return None;
+    }
+
+    // 依次放入表格，跳过被合并的位置
+    let mut rows: Vec<Vec<Option<TableCell>>> = Vec::new();
+    let (mut x, mut y) = (0, 0);
+    for raw in cells.iter() {
+        for _ in 0..raw.spec.repeat {
+            loop {
+                if rows.len() <= y {
+                    rows.resize(y + 1, vec![None; w]);
+                }
+                if x >= w {
+                    x = 0;
+                    y += 1;
+                    continue;
+                }
+                if rows[y][x].is_none() {
+                    break;
+                }
+                x += 1;
+            }
+            let colspan = raw.spec.colspan.min(w - x);
+            let rowspan = raw.spec.rowspan;
+            if rows.len() < y + rowspan {
+                rows.resize(y + rowspan, vec![None; w]);
+            }
+            for row in rows.iter_mut().skip(y).take(rowspan) {
+                for cell in row.iter_mut().skip(x).take(colspan) {
+                    *cell = Some(TableCell {
+                        covered: true,
+                        ..Default::default()
+                    });
+                }
+            }
+            rows[y][x] = Some(TableCell {
+                text: raw.text.clone(),
+                colspan,
+                rowspan,
+                style: raw.spec.style,
+                halign: raw.spec.halign,
+                valign: raw.spec.valign,
+                covered: false,
+            });
+            x += colspan;
+        }
+    }
+
+    let mut data = TableData::new(w, rows.len(), cell_max_w, line_max_w);
+    for (y, row) in rows.into_iter().enumerate() {
+        for (x, cell) in row.into_iter().enumerate() {
+            if let (Some(cell), Some(target)) = (cell, data.cell_at_mut(x, y)) {
+                *target = cell;
+            }
+        }
+    }
+    for (x, column) in columns.into_iter().enumerate() {
+        if let Some(target) = data.column_mut(x) {
+            *target = column;
+        }
+    }
+
+    // 第一行后面紧跟空行时是隐式表头
+    let body_lines: Vec<&str> = body.lines().collect();
+    let implicit_header = body_lines
+        .get(first_line + 1)
+        .is_some_and(|l| l.trim().is_empty());
+    data.header = !has_option(&attributes, "noheader")
+        && (has_option(&attributes, "header") || implicit_header);
+    data.title = title;
+    data.attributes = attributes;
+    data.trim();
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_spec() {
+        let spec = CellSpec::parse("2.3+^.>a").unwrap();
+        assert_eq!((spec.colspan, spec.rowspan), (2, 3));
+        assert_eq!(spec.halign, Some(Align::Center));
+        assert_eq!(spec.valign, Some(VAlign::Bottom));
+        assert_eq!(spec.style, CellStyle::AsciiDoc);
+        assert_eq!(CellSpec::parse("3*").unwrap().repeat, 3);
+        assert_eq!(CellSpec::parse(".2+").unwrap().rowspan, 2);
+        assert!(CellSpec::parse("10").is_none());
+        assert!(CellSpec::parse("word").is_none());
+
+        let columns = parse_cols("<,2*^.>,>3m");
+        assert_eq!(columns.len(), 4);
+        assert_eq!(columns[2].align, Align::Center);
+        assert_eq!(columns[3].to_asciidoc(), ">3m");
+        assert_eq!(parse_cols("3").len(), 3);
+
+        let attributes = split_attributes("[cols=\"1,2\", options=\"header,footer\"]");
+        assert_eq!(
+            attributes,
+            vec!["cols=\"1,2\"", "options=\"header,footer\""]
+        );
+        assert!(has_option(&attributes, "footer"));
+        assert!(has_option(&["%header%autowidth".to_string()], "header"));
+    }
+
+    #[test]
+    fn test_parse_spans() {
+        let text = ".Spans\n\
+                    [cols=\"3*\",options=\"header\"]\n\
+                    |===\n\
+                    | a | b | c\n\
+                    2+| wide .2+| tall\n\
+                    m| code a| * item\n\
+                    * item 2\n\
+                    |===";
+        let data = parse_asciidoc(text, 40, 99).unwrap();
+        assert_eq!((data.w, data.h), (3, 3));
+        assert_eq!(data.title, ".Spans");
+        assert!(data.header);
+
+        let wide = data.cell_at(0, 1).unwrap();
+        assert_eq!((wide.text.as_str(), wide.colspan), ("wide", 2));
+        assert!(data.cell_at(1, 1).unwrap().covered);
+        assert_eq!(data.cell_at(2, 1).unwrap().rowspan, 2);
+        assert!(data.cell_at(2, 2).unwrap().covered);
+        assert_eq!(data.cell_at(0, 2).unwrap().style, CellStyle::Monospace);
+        assert_eq!(data.cell(1, 2), Some("* item\n* item 2"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = "\
+.Title
+[cols=\"<,^2,>m\",options=\"header\"]
+|==============
+| a   |  b  |    c
+
+2+| wide      .2+| tall
+| x   a|  y
+|==============
+";
+        let data = parse_asciidoc(text, 40, 99).unwrap();
+        let output = data.to_asciidoc_table();
+        assert_eq!(output, text);
+        let again = parse_asciidoc(&output, 40, 99).unwrap();
+        assert_eq!(again.to_asciidoc_table(), output);
+    }
+
+    #[test]
+    fn test_implicit_header() {
+        let data = parse_asciidoc("|===\n| a | b\n\n| 1 | 2\n|===", 40, 99).unwrap();
+        assert!(data.header);
+        assert_eq!(data.cell(1, 1), Some("2"));
+        let data = parse_asciidoc("|===\n| a | b\n| 1 | 2\n|===", 40, 99).unwrap();
+        assert!(!data.header);
+        // 转义的 | 不拆分
+        let data = parse_asciidoc("|===\n| a \\| b | c\n|===", 40, 99).unwrap();
+        assert_eq!(data.cell(0, 0), Some("a \\| b"));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::core::BoxBorder;
+
+// 按 上、下、左、右 四个方向是否有连线排列的 16 个字符
+const SINGLE: &str = " ───│┌┐┬│└┘┴│├┤┼";
+const SINGLE_HEADER: &str = " ═══│╒╕╤│╘╛╧│╞╡╪";
+const ROUNDED: &str = " ───│╭╮┬│╰╯┴│├┤┼";
+const DOUBLE: &str = " ═══║╔╗╦║╚╝╩║╠╣╬";
+const HEAVY: &str = " ━━━┃┏┓┳┃┗┛┻┃┣┫╋";
+const ASCII: &str = " ---|+++|+++|+++";
+const ASCII_HEADER: &str = " ===|+++|+++|+++";
+
+/// 根据四个方向的连线选择制表符，单线和圆角表格的表头下方使用双线
+pub fn box_glyph(
+    border: BoxBorder,
+    header: bool,
+    up: bool,
+    down: bool,
+    left: bool,
+    right: bool,
+) -> char {
+    let glyphs = match (border, header) {
+        (BoxBorder::Single, false) => SINGLE,
+        (BoxBorder::Single | BoxBorder::Rounded, true) => SINGLE_HEADER,
+        (BoxBorder::Rounded, false) => ROUNDED,
+        (BoxBorder::Double, _) => DOUBLE,
+        (BoxBorder::Heavy, _) => HEAVY,
+        (BoxBorder::Ascii, false) => ASCII,
+        (BoxBorder::Ascii, true) => ASCII_HEADER,
+    };
+    let index = (up as usize) << 3 | (down as usize) << 2 | (left as usize) << 1 | right as usize;
+    glyphs.chars().nth(index).unwrap_or(' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box_glyph() {
+        assert_eq!(
+            box_glyph(BoxBorder::Single, false, false, true, false, true),
+            '┌'
+        );
+        assert_eq!(
+            box_glyph(BoxBorder::Single, true, true, true, true, true),
+            '╪'
+        );
+        assert_eq!(
+            box_glyph(BoxBorder::Rounded, false, true, false, true, false),
+            '╯'
+        );
+        assert_eq!(
+            box_glyph(BoxBorder::Double, false, true, true, false, true),
+            '╠'
+        );
+        assert_eq!(
+            box_glyph(BoxBorder::Heavy, false, false, true, true, true),
+            '┳'
+        );
+        assert_eq!(
+            box_glyph(BoxBorder::Ascii, true, false, false, true, true),
+            '='
+        );
+    }
+}
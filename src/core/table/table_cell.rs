@@ -0,0 +1,196 @@
+use super::table_data::Align;
+
+/// 单元格的垂直对齐方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VAlign {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl VAlign {
+    /// 解析 asciidoc 中 `.` 之后的对齐符号
+    pub fn from_asciidoc(symbol: char) -> Option<Self> {
+        match symbol {
+            '<' => Some(VAlign::Top),
+            '^' => Some(VAlign::Middle),
+            '>' => Some(VAlign::Bottom),
+            _ => None,
+        }
+    }
+
+    pub fn to_asciidoc(&self) -> char {
+        match self {
+            VAlign::Top => '<',
+            VAlign::Middle => '^',
+            VAlign::Bottom => '>',
+        }
+    }
+}
+
+/// asciidoc 单元格样式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CellStyle {
+    #[default]
+    Default,
+    /// `a`，内容按 asciidoc 文档解析
+    AsciiDoc,
+    /// `e`
+    Emphasis,
+    /// `h`
+    Header,
+    /// `l`
+    Literal,
+    /// `m`
+    Monospace,
+    /// `s`
+    Strong,
+}
+
+impl CellStyle {
+    pub fn from_asciidoc(symbol: char) -> Option<Self> {
+        match symbol {
+            'd' => Some(CellStyle::Default),
+            'a' => Some(CellStyle::AsciiDoc),
+            'e' => Some(CellStyle::Emphasis),
+            'h' => Some(CellStyle::Header),
+            'l' => Some(CellStyle::Literal),
+            'm' => Some(CellStyle::Monospace),
+            's' => Some(CellStyle::Strong),
+            _ => None,
+        }
+    }
+
+    pub fn to_asciidoc(&self) -> char {
+        match self {
+            CellStyle::Default => 'd',
+            CellStyle::AsciiDoc => 'a',
+            CellStyle::Emphasis => 'e',
+            CellStyle::Header => 'h',
+            CellStyle::Literal => 'l',
+            CellStyle::Monospace => 'm',
+            CellStyle::Strong => 's',
+        }
+    }
+}
+
+/// 表格中的一个单元格
+///
+/// 合并单元格记录在左上角的单元格中，被它覆盖的单元格 `covered` 为 true。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableCell {
+    pub text: String,
+    pub colspan: usize,
+    pub rowspan: usize,
+    pub style: CellStyle,
+    /// 单元格自己的对齐方式，None 时使用列的设置
+    pub halign: Option<Align>,
+    pub valign: Option<VAlign>,
+    /// 被其他单元格合并覆盖
+    pub covered: bool,
+}
+
+impl Default for TableCell {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            colspan: 1,
+            rowspan: 1,
+            style: CellStyle::default(),
+            halign: None,
+            valign: None,
+            covered: false,
+        }
+    }
+}
+
+impl TableCell {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_merged(&self) -> bool {
+        self.colspan > 1 || self.rowspan > 1
+    }
+
+    /// asciidoc 的单元格说明符，例如 `2.3+^.>a`，默认单元格为空
+    pub fn asciidoc_spec(&self) -> String {
+        let mut spec = String::new();
+        if self.is_merged() {
+            if self.colspan > 1 {
+                spec.push_str(&self.colspan.to_string());
+            }
+            if self.rowspan > 1 {
+                spec.push('.');
+                spec.push_str(&self.rowspan.to_string());
+            }
+            spec.push('+');
+        }
+        if let Some(align) = self.halign {
+            spec.push(align.to_asciidoc());
+        }
+        if let Some(valign) = self.valign {
+            spec.push('.');
+            spec.push(valign.to_asciidoc());
+        }
+        if self.style != CellStyle::Default {
+            spec.push(self.style.to_asciidoc());
+        }
+        spec
+    }
+}
+
+/// asciidoc cols 属性中的一列
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ColumnSpec {
+    pub align: Align,
+    pub valign: Option<VAlign>,
+    /// 相对宽度或百分比，例如 `2`、`15%`，`~` 表示自动宽度
+    pub width: Option<String>,
+    pub style: CellStyle,
+    /// org 宽度标记 `<N>`，超出的内容在 Emacs 中折叠显示
+    pub max_width: Option<usize>,
+}
+
+impl ColumnSpec {
+    /// 解析一列的说明，例如 `^.>2m`
+    pub fn from_asciidoc(spec: &str) -> Self {
+        let mut col = ColumnSpec::default();
+        let mut width = String::new();
+        let mut chars = spec.trim().chars();
+        while let Some(c) = chars.next() {
+            if c == '.' {
+                col.valign = chars.next().and_then(VAlign::from_asciidoc);
+            } else if let Some(align) = Align::from_asciidoc(c) {
+                col.align = align;
+            } else if c.is_ascii_digit() || c == '%' || c == '~' {
+                width.push(c);
+            } else if let Some(style) = CellStyle::from_asciidoc(c) {
+                col.style = style;
+            }
+        }
+        if !width.is_empty() {
+            col.width = Some(width);
+        }
+        col
+    }
+
+    pub fn to_asciidoc(&self) -> String {
+        let mut spec = self.align.to_asciidoc().to_string();
+        if let Some(valign) = self.valign {
+            spec.push('.');
+            spec.push(valign.to_asciidoc());
+        }
+        if let Some(width) = &self.width {
+            spec.push_str(width);
+        }
+        if self.style != CellStyle::Default {
+            spec.push(self.style.to_asciidoc());
+        }
+        spec
+    }
+}
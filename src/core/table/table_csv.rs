@@ -0,0 +1,134 @@
+use super::TableData;
+
+/// 自动识别时尝试的分隔符，靠前的优先
+pub const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+
+/// 按照 RFC 4180 解析 csv，支持引号中的分隔符、换行和 `""` 转义
+pub fn parse_records(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            // 只有字段开头的引号才算引用
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    // 空行不算作记录
+    records.retain(|r| !(r.len() == 1 && r[0].trim().is_empty()));
+    records
+}
+
+/// 识别分隔符：第一条记录至少两列，并且 60% 以上的记录列数相同
+pub fn detect_delimiter(text: &str) -> Option<char> {
+    DELIMITERS.into_iter().find(|d| {
+        let records = parse_records(text, *d);
+        let Some(first) = records.first() else {
+            return false;
+        };
+        let same = records.iter().filter(|r| r.len() == first.len()).count();
+        first.len() > 1 && same * 10 >= records.len() * 6
+    })
+}
+
+/// 把 csv 或 tsv 解析为表格，列数取最长的记录
+pub fn parse_delimited(
+    text: &str,
+    delimiter: char,
+    cell_max_w: usize,
+    line_max_w: usize,
+) -> Option<TableData> {
+    let records = parse_records(text, delimiter);
+    let w = records.iter().map(|r| r.len()).max()?;
+    let mut data = TableData::new(w, records.len(), cell_max_w, line_max_w);
+    for (y, record) in records.iter().enumerate() {
+        for (x, field) in record.iter().enumerate() {
+            data.set_cell(x, y, field);
+        }
+    }
+    data.header = true;
+    data.trim();
+    Some(data)
+}
+
+/// 生成一条记录，包含分隔符、引号或者换行的字段用引号包起来
+pub fn write_record(fields: &[&str], delimiter: char) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|f| {
+            if f.contains([delimiter, '"', '\n', '\r']) {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f.to_string()
+            }
+        })
+        .collect();
+    fields.join(&delimiter.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_records() {
+        let text = "name,note\r\n\"Doe, John\",\"say \"\"hi\"\"\"\n\"a\nb\",c\n";
+        assert_eq!(
+            parse_records(text, ','),
+            vec![
+                vec!["name", "note"],
+                vec!["Doe, John", "say \"hi\""],
+                vec!["a\nb", "c"],
+            ]
+        );
+        assert_eq!(
+            parse_records("a\t\"x\ty\"\n", '\t'),
+            vec![vec!["a", "x\ty"]]
+        );
+    }
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(detect_delimiter("a,b,c\n1,2,3"), Some(','));
+        assert_eq!(detect_delimiter("a;b\n\"1;5\";2"), Some(';'));
+        assert_eq!(detect_delimiter("a\tb\n1,5\t2"), Some('\t'));
+        assert_eq!(detect_delimiter("a|b\n1|2"), Some('|'));
+        assert_eq!(detect_delimiter("one line of text\nand more"), None);
+    }
+
+    #[test]
+    fn test_write_record() {
+        assert_eq!(
+            write_record(&["a", "b,c", "say \"hi\""], ','),
+            "a,\"b,c\",\"say \"\"hi\"\"\""
+        );
+        assert_eq!(write_record(&["a,b", "c\td"], '\t'), "a,b\t\"c\td\"");
+    }
+}
@@ -1,31 +1,167 @@
 use std::cmp;
+use std::ops::Range;
 
+use super::table_asciidoc::{cols_value, parse_cols};
+use super::table_box::box_glyph;
+use super::table_cell::{CellStyle, ColumnSpec, TableCell};
+use super::table_csv::write_record;
+use super::table_edit::{SortMode, TableOp, compare};
 use super::table_format::MarkdownStyle;
+use super::table_html::escape_html;
+use super::table_latex::{column_spec, escape_latex};
+use super::table_org::cookie;
+use super::table_wrap::{fit_widths, wrap_text};
+use crate::core::BoxBorder;
 use crate::utils;
 
-#[derive(Debug)]
+/// 列的水平对齐方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// 数字按小数点对齐，整体靠右
+    Decimal,
+}
+
+impl Align {
+    /// 解析 markdown 的对齐行单元格，例如 `:--`、`:-:`、`--:`
+    pub fn from_markdown(cell: &str) -> Option<Self> {
+        let cell = cell.trim();
+        let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+        if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+            return None;
+        }
+        match (cell.starts_with(':'), cell.ends_with(':')) {
+            (true, true) => Some(Align::Center),
+            (false, true) => Some(Align::Right),
+            _ => Some(Align::Left),
+        }
+    }
+
+    /// 生成指定宽度的 markdown 对齐行单元格，markdown 没有小数点对齐，使用右对齐
+    pub fn to_markdown(&self, width: usize) -> String {
+        let width = cmp::max(width, 3);
+        match self {
+            Align::Left => "-".repeat(width),
+            Align::Center => format!(":{}:", "-".repeat(width - 2)),
+            Align::Right | Align::Decimal => format!("{}:", "-".repeat(width - 1)),
+        }
+    }
+
+    /// 解析 asciidoc cols 属性中的对齐符号
+    pub fn from_asciidoc(symbol: char) -> Option<Self> {
+        match symbol {
+            '<' => Some(Align::Left),
+            '^' => Some(Align::Center),
+            '>' => Some(Align::Right),
+            _ => None,
+        }
+    }
+
+    /// asciidoc cols 属性中的对齐符号，asciidoc 没有小数点对齐，使用右对齐
+    pub fn to_asciidoc(&self) -> char {
+        match self {
+            Align::Left => '<',
+            Align::Center => '^',
+            Align::Right | Align::Decimal => '>',
+        }
+    }
+}
+
+// 可以按小数点对齐的数字，例如 -1,234.5
+fn is_number(s: &str) -> bool {
+    let s = s.trim_start_matches(['+', '-']);
+    s.chars().any(|c| c.is_ascii_digit())
+        && s.chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+}
+
+// 多行文本取最宽的一行
+fn text_width(s: &str) -> usize {
+    s.lines().map(utils::cn_length).max().unwrap_or(0)
+}
+
+// 数字拆分为整数部分和小数部分（包含小数点）
+fn split_decimal(s: &str) -> (&str, &str) {
+    match s.find('.') {
+        Some(i) => s.split_at(i),
+        None => (s, ""),
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct TableData {
     pub title: String,
     pub w: usize,
     pub h: usize,
+    /// 第一行是表头
+    pub header: bool,
+    /// asciidoc 属性行中的属性，按原文的顺序保存，例如 `%header`、`cols="2*"`
+    pub attributes: Vec<String>,
+    /// org 分隔线的位置，值为分隔线上方的行数
+    pub rules: Vec<usize>,
+    /// org 表格下方的 `#+TBLFM:` 公式行，输出 org 时保留
+    pub formulas: Vec<String>,
     cell_max_w: usize,
     line_max_w: usize,
-    data: Vec<Vec<String>>,
+    data: Vec<Vec<TableCell>>,
+    // 每一列的对齐方式和 asciidoc 列设置
+    columns: Vec<ColumnSpec>,
 }
 
 impl TableData {
     pub fn new(w: usize, h: usize, cell_max_w: usize, line_max_w: usize) -> Self {
-        let data = vec![vec!["".to_string(); w]; h];
+        let data = vec![vec![TableCell::default(); w]; h];
         Self {
             title: "".to_string(),
             w,
             h,
+            header: false,
+            attributes: Vec::new(),
+            rules: Vec::new(),
+            formulas: Vec::new(),
             cell_max_w,
             line_max_w,
             data,
+            columns: vec![ColumnSpec::default(); w],
         }
     }
 
+    /// 获取 x 列的对齐方式，越界时返回默认的左对齐
+    pub fn align(&self, x: usize) -> Align {
+        self.columns.get(x).map(|c| c.align).unwrap_or_default()
+    }
+
+    /// 设置 x 列的对齐方式，当越界时不会赋值
+    pub fn set_align(&mut self, x: usize, align: Align) {
+        if x < self.w {
+            self.columns[x].align = align;
+        }
+    }
+
+    pub fn column(&self, x: usize) -> Option<&ColumnSpec> {
+        self.columns.get(x)
+    }
+
+    pub fn column_mut(&mut self, x: usize) -> Option<&mut ColumnSpec> {
+        self.columns.get_mut(x)
+    }
+
+    /// 获取 x 列 y 行的值，越界时返回 None
+    pub fn cell(&self, x: usize, y: usize) -> Option<&str> {
+        self.cell_at(x, y).map(|c| c.text.as_str())
+    }
+
+    pub fn cell_at(&self, x: usize, y: usize) -> Option<&TableCell> {
+        self.data.get(y)?.get(x)
+    }
+
+    pub fn cell_at_mut(&mut self, x: usize, y: usize) -> Option<&mut TableCell> {
+        self.data.get_mut(y)?.get_mut(x)
+    }
+
     /// 给 x 列 y 行设置值，当越界时不会赋值
     pub fn set_cell(&mut self, x: usize, y: usize, v: &str) {
         if y >= self.h {
@@ -34,29 +170,434 @@ impl TableData {
         if x >= self.w {
             return;
         }
-        self.data[y][x] = v.trim().to_string().clone();
+        self.data[y][x].text = v.trim().to_string();
     }
 
-    /// 计算指定列的最大宽度，当越界时返回 0
-    fn width(&self, x: usize, is_markdown: bool) -> usize {
-        let mut v: usize = 0;
+    /// 是否有合并的单元格
+    pub fn has_merged_cells(&self) -> bool {
+        self.data.iter().flatten().any(|c| c.is_merged())
+    }
+
+    /// 找到覆盖 x 列 y 行的合并单元格的左上角，没有合并时返回自身
+    pub fn merge_origin(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        let cell = self.cell_at(x, y)?;
+        if !cell.covered {
+            return Some((x, y));
+        }
+        for oy in (0..=y).rev() {
+            for ox in (0..=x).rev() {
+                let origin = &self.data[oy][ox];
+                if !origin.covered
+                    && origin.is_merged()
+                    && ox + origin.colspan > x
+                    && oy + origin.rowspan > y
+                {
+                    return Some((ox, oy));
+                }
+            }
+        }
+        None
+    }
+
+    /// 把从 x 列 y 行开始的 colspan 列 rowspan 行合并为一个单元格
+    ///
+    /// 只保留左上角单元格的内容，范围越界或者和其他合并单元格部分重叠时返回 false
+    pub fn merge(&mut self, x: usize, y: usize, colspan: usize, rowspan: usize) -> bool {
+        if colspan == 0 || rowspan == 0 || x + colspan > self.w || y + rowspan > self.h {
+            return false;
+        }
+        // 范围内已有的合并单元格必须完全包含在范围内
+        for cy in y..y + rowspan {
+            for cx in x..x + colspan {
+                let Some((ox, oy)) = self.merge_origin(cx, cy) else {
+                    return false;
+                };
+                let origin = &self.data[oy][ox];
+                if ox < x
+                    || oy < y
+                    || ox + origin.colspan > x + colspan
+                    || oy + origin.rowspan > y + rowspan
+                {
+                    return false;
+                }
+            }
+        }
+        let origin = self.data[y][x].clone();
+        for cy in y..y + rowspan {
+            for cx in x..x + colspan {
+                self.data[cy][cx] = TableCell {
+                    covered: true,
+                    ..Default::default()
+                };
+            }
+        }
+        self.data[y][x] = TableCell {
+            colspan,
+            rowspan,
+            covered: false,
+            ..origin
+        };
+        true
+    }
+
+    /// 拆分覆盖 x 列 y 行的合并单元格，没有合并时返回 false
+    pub fn unmerge(&mut self, x: usize, y: usize) -> bool {
+        let Some((ox, oy)) = self.merge_origin(x, y) else {
+            return false;
+        };
+        let (colspan, rowspan) = (self.data[oy][ox].colspan, self.data[oy][ox].rowspan);
+        if colspan == 1 && rowspan == 1 {
+            return false;
+        }
+        for row in self.data.iter_mut().skip(oy).take(rowspan) {
+            for cell in row.iter_mut().skip(ox).take(colspan) {
+                cell.covered = false;
+            }
+        }
+        let cell = &mut self.data[oy][ox];
+        cell.colspan = 1;
+        cell.rowspan = 1;
+        true
+    }
+
+    // 表头所在的行不参与行的插入、删除、移动和排序
+    fn body_start(&self) -> usize {
+        usize::from(self.header)
+    }
+
+    // 范围内的行没有被跨行的合并单元格穿过
+    fn rows_are_plain(&self, rows: Range<usize>) -> bool {
+        rows.flat_map(|y| (0..self.w).map(move |x| (x, y)))
+            .all(|(x, y)| {
+                self.data[y][x].rowspan == 1
+                    && self.merge_origin(x, y).is_some_and(|(_, oy)| oy == y)
+            })
+    }
+
+    // 范围内的列没有被跨列的合并单元格穿过
+    fn columns_are_plain(&self, columns: Range<usize>) -> bool {
+        columns
+            .flat_map(|x| (0..self.h).map(move |y| (x, y)))
+            .all(|(x, y)| {
+                self.data[y][x].colspan == 1
+                    && self.merge_origin(x, y).is_some_and(|(ox, _)| ox == x)
+            })
+    }
+
+    /// 在 y 行之前插入空行，y 等于行数时添加到最后，插入到合并单元格中间时合并单元格变高
+    pub fn insert_row(&mut self, y: usize) -> bool {
+        if y < self.body_start() || y > self.h {
+            return false;
+        }
+        let mut row = vec![TableCell::default(); self.w];
+        for (x, cell) in row.iter_mut().enumerate() {
+            if y == self.h || !self.data[y][x].covered {
+                continue;
+            }
+            if let Some((ox, oy)) = self.merge_origin(x, y)
+                && oy < y
+            {
+                cell.covered = true;
+                if ox == x {
+                    self.data[oy][ox].rowspan += 1;
+                }
+            }
+        }
+        // 最后的分隔线保持在最后
+        let h = self.h;
+        for r in self.rules.iter_mut() {
+            if *r > y || *r == h {
+                *r += 1;
+            }
+        }
+        self.data.insert(y, row);
+        self.h += 1;
+        true
+    }
+
+    /// 删除 y 行，合并单元格从这一行开始时内容移到下一行
+    pub fn delete_row(&mut self, y: usize) -> bool {
+        if y < self.body_start() || y >= self.h || self.h == 1 {
+            return false;
+        }
+        for x in 0..self.w {
+            let cell = self.data[y][x].clone();
+            if !cell.covered {
+                if cell.rowspan > 1 {
+                    self.data[y + 1][x] = TableCell {
+                        rowspan: cell.rowspan - 1,
+                        ..cell
+                    };
+                }
+            } else if let Some((ox, oy)) = self.merge_origin(x, y)
+                && oy < y
+                && ox == x
+            {
+                self.data[oy][ox].rowspan -= 1;
+            }
+        }
+        for r in self.rules.iter_mut() {
+            if *r > y {
+                *r -= 1;
+            }
+        }
+        self.rules.dedup();
+        self.data.remove(y);
+        self.h -= 1;
+        true
+    }
+
+    /// 把 from 行移动到 to 行，经过的行中有跨行的合并单元格时返回 false
+    pub fn move_row(&mut self, from: usize, to: usize) -> bool {
+        let start = self.body_start();
+        if from == to || from.min(to) < start || from.max(to) >= self.h {
+            return false;
+        }
+        if !self.rows_are_plain(from.min(to)..from.max(to) + 1) {
+            return false;
+        }
+        let row = self.data.remove(from);
+        self.data.insert(to, row);
+        true
+    }
+
+    /// 在 x 列之前插入空列，x 等于列数时添加到最后，插入到合并单元格中间时合并单元格变宽
+    pub fn insert_column(&mut self, x: usize) -> bool {
         if x > self.w {
-            return v;
+            return false;
         }
-        for (i, line) in self.data.iter().enumerate() {
-            if is_markdown && i == 1 {
-                let mut txt = String::new();
-                for x in line.iter() {
-                    txt.push_str(x.trim());
-                    txt.push('\n');
+        for y in 0..self.h {
+            let mut cell = TableCell::default();
+            if x < self.w
+                && self.data[y][x].covered
+                && let Some((ox, oy)) = self.merge_origin(x, y)
+                && ox < x
+            {
+                cell.covered = true;
+                if oy == y {
+                    self.data[oy][ox].colspan += 1;
                 }
-                if txt.contains("-") {
-                    continue;
+            }
+            self.data[y].insert(x, cell);
+        }
+        self.columns.insert(x, ColumnSpec::default());
+        self.w += 1;
+        true
+    }
+
+    /// 删除 x 列，合并单元格从这一列开始时内容移到下一列
+    pub fn delete_column(&mut self, x: usize) -> bool {
+        if x >= self.w || self.w == 1 {
+            return false;
+        }
+        for y in 0..self.h {
+            let cell = self.data[y][x].clone();
+            if !cell.covered {
+                if cell.colspan > 1 {
+                    self.data[y][x + 1] = TableCell {
+                        colspan: cell.colspan - 1,
+                        ..cell
+                    };
                 }
+            } else if let Some((ox, oy)) = self.merge_origin(x, y)
+                && ox < x
+                && oy == y
+            {
+                self.data[oy][ox].colspan -= 1;
             }
-            v = std::cmp::max(v, utils::cn_length(&line[x]));
         }
-        return v;
+        for line in self.data.iter_mut() {
+            line.remove(x);
+        }
+        self.columns.remove(x);
+        self.w -= 1;
+        true
+    }
+
+    /// 把 from 列移动到 to 列，经过的列中有跨列的合并单元格时返回 false
+    pub fn move_column(&mut self, from: usize, to: usize) -> bool {
+        if from == to || from.max(to) >= self.w {
+            return false;
+        }
+        if !self.columns_are_plain(from.min(to)..from.max(to) + 1) {
+            return false;
+        }
+        for line in self.data.iter_mut() {
+            let cell = line.remove(from);
+            line.insert(to, cell);
+        }
+        let column = self.columns.remove(from);
+        self.columns.insert(to, column);
+        true
+    }
+
+    /// 行列互换，列的对齐方式保存到单元格中
+    pub fn transpose(&mut self) {
+        let mut data = vec![vec![TableCell::default(); self.h]; self.w];
+        for (y, line) in self.data.iter().enumerate() {
+            for (x, cell) in line.iter().enumerate() {
+                let mut cell = cell.clone();
+                std::mem::swap(&mut cell.colspan, &mut cell.rowspan);
+                if cell.halign.is_none() && self.align(x) != Align::Left {
+                    cell.halign = Some(self.align(x));
+                }
+                data[x][y] = cell;
+            }
+        }
+        self.data = data;
+        std::mem::swap(&mut self.w, &mut self.h);
+        self.columns = vec![ColumnSpec::default(); self.w];
+        self.attributes.retain(|attr| cols_value(attr).is_none());
+        self.rules.clear();
+    }
+
+    /// 按 x 列稳定排序，表头保持在第一行，有跨行的合并单元格时返回 false
+    pub fn sort_by_column(&mut self, x: usize, mode: SortMode, descending: bool) -> bool {
+        let start = self.body_start();
+        if x >= self.w || !self.rows_are_plain(start..self.h) {
+            return false;
+        }
+        // 被跨列单元格覆盖时使用合并单元格的内容
+        let key = |line: &[TableCell]| {
+            line[..=x]
+                .iter()
+                .rev()
+                .find(|c| !c.covered)
+                .map(|c| c.text.clone())
+                .unwrap_or_default()
+        };
+        let mut body = self.data.split_off(start);
+        body.sort_by(|a, b| {
+            let ordering = compare(&key(a), &key(b), mode);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        self.data.extend(body);
+        true
+    }
+
+    /// 删除重复的行，返回删除的行数，有跨行的合并单元格时不删除
+    pub fn dedup_rows(&mut self) -> usize {
+        let start = self.body_start();
+        if !self.rows_are_plain(start..self.h) {
+            return 0;
+        }
+        let mut body: Vec<Vec<TableCell>> = Vec::new();
+        for line in self.data.split_off(start) {
+            if !body.contains(&line) {
+                body.push(line);
+            }
+        }
+        self.data.extend(body);
+        let removed = self.h - self.data.len();
+        self.h = self.data.len();
+        let h = self.h;
+        self.rules.retain(|r| *r <= h);
+        removed
+    }
+
+    /// 执行编辑操作，操作无效时返回 false
+    pub fn apply(&mut self, op: &TableOp) -> bool {
+        match op {
+            TableOp::InsertRow(y) => self.insert_row(*y),
+            TableOp::DeleteRow(y) => self.delete_row(*y),
+            TableOp::MoveRow(from, to) => self.move_row(*from, *to),
+            TableOp::InsertColumn(x) => self.insert_column(*x),
+            TableOp::DeleteColumn(x) => self.delete_column(*x),
+            TableOp::MoveColumn(from, to) => self.move_column(*from, *to),
+            TableOp::Transpose => {
+                self.transpose();
+                true
+            }
+            TableOp::Sort {
+                column,
+                mode,
+                descending,
+            } => self.sort_by_column(*column, *mode, *descending),
+            TableOp::Dedup => self.dedup_rows() > 0,
+        }
+    }
+
+    /// 单元格的对齐方式，单元格没有设置时使用列的对齐方式
+    fn cell_align(&self, x: usize, y: usize) -> Align {
+        self.cell_at(x, y)
+            .and_then(|c| c.halign)
+            .unwrap_or_else(|| self.align(x))
+    }
+
+    /// 计算指定列的最大宽度，当越界时返回 0
+    ///
+    /// spans 为 false 时按照没有合并的单元格计算
+    fn width(&self, x: usize, spans: bool) -> usize {
+        if x >= self.w {
+            return 0;
+        }
+        let mut v: usize = 0;
+        for line in self.data.iter() {
+            // 跨列的单元格在 cell_line_widths 中单独计算
+            if spans && (line[x].covered || line[x].colspan > 1) {
+                continue;
+            }
+            v = cmp::max(v, text_width(&line[x].text));
+        }
+        if self.align(x) == Align::Decimal {
+            let (int_w, frac_w) = self.decimal_widths(x);
+            v = cmp::max(v, int_w + frac_w);
+        }
+        v
+    }
+
+    // 小数点对齐时，整数部分和小数部分的最大宽度，多行单元格按行计算，与 pad_text 一致
+    fn decimal_widths(&self, x: usize) -> (usize, usize) {
+        let (mut int_w, mut frac_w) = (0, 0);
+        for text in self.data.iter().flat_map(|line| line[x].text.lines()) {
+            if !is_number(text) {
+                continue;
+            }
+            let (int, frac) = split_decimal(text);
+            int_w = cmp::max(int_w, int.len());
+            frac_w = cmp::max(frac_w, frac.len());
+        }
+        (int_w, frac_w)
+    }
+
+    /// 按照对齐方式把 x 列 y 行的单元格补齐到 width 宽度
+    fn pad_cell(&self, x: usize, y: usize, width: usize) -> String {
+        self.pad_text(x, y, self.cell(x, y).unwrap_or_default(), width)
+    }
+
+    /// 按照 x 列 y 行的对齐方式把 cell 补齐到 width 宽度，用于多行单元格中的一行
+    fn pad_text(&self, x: usize, y: usize, cell: &str, width: usize) -> String {
+        let len = text_width(cell);
+        if len >= width {
+            return cell.to_string();
+        }
+        let blank = width - len;
+        match self.cell_align(x, y) {
+            Align::Left => format!("{}{}", cell, " ".repeat(blank)),
+            Align::Right => format!("{}{}", " ".repeat(blank), cell),
+            Align::Center => format!(
+                "{}{}{}",
+                " ".repeat(blank / 2),
+                cell,
+                " ".repeat(blank - blank / 2)
+            ),
+            Align::Decimal if is_number(cell) => {
+                let (int_w, frac_w) = self.decimal_widths(x);
+                let (int, frac) = split_decimal(cell);
+                let text = format!(
+                    "{}{}{}",
+                    " ".repeat(int_w.saturating_sub(int.len())),
+                    cell,
+                    " ".repeat(frac_w.saturating_sub(frac.len()))
+                );
+                format!("{}{}", " ".repeat(width.saturating_sub(text.len())), text)
+            }
+            Align::Decimal => format!("{}{}", " ".repeat(blank), cell),
+        }
     }
 
     fn max_line_width(&self) -> usize {
@@ -64,38 +605,98 @@ impl TableData {
 
         for line in self.data.iter() {
             let mut cur_width: usize = 0;
-            for cell in line.iter() {
-                cur_width += 3 + utils::cn_length(cell);
+            for cell in line.iter().filter(|c| !c.covered) {
+                cur_width += 3 + text_width(&cell.text);
             }
             w = cmp::max(w, cur_width);
         }
         return cmp::min(w, self.line_max_w);
     }
 
-    fn cell_line_widths(&self, is_markdown: bool) -> Vec<usize> {
+    fn cell_line_widths(&self, spans: bool) -> Vec<usize> {
+        self.column_widths(spans, self.cell_max_w)
+    }
+
+    /// 各列的宽度，不超过 max_w，不能折行的格式使用 usize::MAX
+    fn column_widths(&self, spans: bool, max_w: usize) -> Vec<usize> {
         let mut cell_widths: Vec<usize> = Vec::new();
         for x in 0..self.w {
             // markdown 对齐需要三格
-            cell_widths.push(std::cmp::min(
-                std::cmp::max(self.width(x, is_markdown), 3),
-                self.cell_max_w,
-            ));
+            cell_widths.push(std::cmp::min(std::cmp::max(self.width(x, spans), 3), max_w));
+        }
+        if !spans {
+            return cell_widths;
+        }
+        // 跨列的单元格放不下时，把差值平均分给跨过的列
+        for line in self.data.iter() {
+            for (x, cell) in line.iter().enumerate() {
+                if cell.covered || cell.colspan < 2 {
+                    continue;
+                }
+                let need = cmp::min(text_width(&cell.text), max_w.saturating_mul(cell.colspan));
+                let have = Self::span_width(&cell_widths, x, cell.colspan, 3);
+                if need <= have {
+                    continue;
+                }
+                let extra = need - have;
+                for (k, w) in cell_widths
+                    .iter_mut()
+                    .skip(x)
+                    .take(cell.colspan)
+                    .enumerate()
+                {
+                    *w += extra / cell.colspan + usize::from(k < extra % cell.colspan);
+                }
+            }
+        }
+        cell_widths
+    }
+
+    // 每列有 3 个字符的边框和空格，再加上行尾的边框，整行超过 line_max_w 时按比例缩小
+    fn fit_line_widths(&self, spans: bool) -> Vec<usize> {
+        let available = self.line_max_w.saturating_sub(3 * self.w + 1);
+        fit_widths(&self.cell_line_widths(spans), available, 3)
+    }
+
+    /// 按列宽折行后的表格，用于支持多行单元格的格式
+    ///
+    /// spans 为 true 时跨列的单元格按合并后的宽度折行
+    fn wrapped(&self, spans: bool) -> TableData {
+        let cell_widths = self.fit_line_widths(spans);
+        let mut data = self.clone();
+        for line in data.data.iter_mut() {
+            for (x, cell) in line.iter_mut().enumerate() {
+                if cell.covered {
+                    continue;
+                }
+                let width = if spans {
+                    Self::span_width(&cell_widths, x, cell.colspan, 3)
+                } else {
+                    cell_widths[x]
+                };
+                cell.text = wrap_text(&cell.text, width);
+            }
         }
-        return cell_widths;
+        data
+    }
+
+    /// 从 x 列开始跨 colspan 列的单元格宽度，包含中间 sep 宽的分隔符
+    fn span_width(cell_widths: &[usize], x: usize, colspan: usize, sep: usize) -> usize {
+        let sum: usize = cell_widths.iter().skip(x).take(colspan).sum();
+        sum + sep * colspan.saturating_sub(1)
+    }
+
+    // 空行：没有内容也不属于合并单元格
+    fn is_empty_line(line: &[TableCell]) -> bool {
+        line.iter()
+            .all(|c| c.text.is_empty() && !c.covered && !c.is_merged())
     }
 
     fn trim_start(&mut self) {
         // 清理首部连续空行
         let mut to_del: Vec<usize> = Vec::new();
         for (i, line) in self.data.iter().enumerate() {
-            let mut flag = false;
-            for text in line.iter() {
-                if text.len() > 0 {
-                    flag = true;
-                    break;
-                }
-            }
-            if !flag {
+            if Self::is_empty_line(line) {
                 to_del.push(i);
             } else {
                 break;
@@ -114,14 +715,7 @@ impl TableData {
         for i in (0..self.data.len()).rev() {
             let line = &self.data[i];
             log::debug!("line: {:?}", line);
-            let mut flag = false;
-            for text in line.iter() {
-                if text.len() > 0 {
-                    flag = true;
-                    break;
-                }
-            }
-            if !flag {
+            if Self::is_empty_line(line) {
                 to_del.push(i);
             } else {
                 break;
@@ -150,100 +744,608 @@ impl TableData {
     }
 
     fn to_markdown_table(&self, style: MarkdownStyle) -> String {
-        let cell_widths = self.cell_line_widths(true);
+        // markdown 不支持合并单元格，内容留在左上角，其余位置为空
+        if self.has_merged_cells() {
+            log::warn!("markdown tables do not support merged cells, spans are dropped");
+        }
+        // markdown 单元格不能换行，先按 line_max_w 分配的列宽折行，再用 <br> 连接，
+        // 列宽按连接后最宽的单元格计算，保证每一行对齐
+        let fitted = self.fit_line_widths(false);
+        let texts: Vec<Vec<String>> = self
+            .wrapped(false)
+            .data
+            .iter()
+            .map(|line| line.iter().map(|c| c.text.replace('\n', "<br>")).collect())
+            .collect();
+        let cell_widths: Vec<usize> = (0..self.w)
+            .map(|j| {
+                texts
+                    .iter()
+                    .map(|line| text_width(&line[j]))
+                    .fold(fitted[j], cmp::max)
+            })
+            .collect();
 
-        let mut content: Vec<String> = Vec::new();
-        // 正文
-        for line in self.data.iter() {
+        let markdown_line = |cells: Vec<String>| {
             let mut xline = String::new();
-            for (j, cell) in line.iter().enumerate() {
-                let (v1, v2) = (cell_widths[j], utils::cn_length(cell));
-                let blank = if v1 > v2 {
-                    " ".repeat(v1 - v2)
-                } else {
-                    "".to_string()
-                };
+            for (j, cell) in cells.iter().enumerate() {
                 if style == MarkdownStyle::Normal || j != 0 {
                     xline.push_str("| ");
                 }
                 xline.push_str(cell);
-                xline.push_str(blank.as_str());
                 xline.push(' ');
             }
             if style == MarkdownStyle::Normal {
                 xline.push('|');
             }
-            content.push(xline.clone());
+            xline
+        };
+
+        let mut content: Vec<String> = Vec::new();
+        // 正文
+        for (i, line) in texts.iter().enumerate() {
+            let cells = line
+                .iter()
+                .enumerate()
+                .map(|(j, text)| self.pad_text(j, i, text, cell_widths[j]))
+                .collect();
+            content.push(markdown_line(cells));
+            // 表头下方添加表格对齐
+            if i == 0 {
+                let cells = cell_widths
+                    .iter()
+                    .enumerate()
+                    .map(|(j, w)| self.align(j).to_markdown(*w))
+                    .collect();
+                content.push(markdown_line(cells));
+            }
+        }
+
+        // 首列右对齐时行首的空格需要保留
+        let mut result = String::new();
+        for x in content.iter() {
+            result.push_str(x.trim_end());
+            result.push('\n');
+        }
+        result
+    }
+
+    pub fn to_csv_table(&self) -> String {
+        self.to_delimited_table(',')
+    }
+
+    pub fn to_tsv_table(&self) -> String {
+        self.to_delimited_table('\t')
+    }
+
+    // csv 不支持合并单元格，被覆盖的位置为空
+    fn to_delimited_table(&self, delimiter: char) -> String {
+        let mut result = String::new();
+        for line in self.data.iter() {
+            let fields: Vec<&str> = line.iter().map(|c| c.text.as_str()).collect();
+            result.push_str(&write_record(&fields, delimiter));
+            result.push('\n');
+        }
+        result
+    }
+
+    pub fn to_html_table(&self) -> String {
+        let mut content: Vec<String> = vec!["<table>".to_string()];
+        if let Some(title) = self.title.strip_prefix('.') {
+            content.push(format!("  <caption>{}</caption>", escape_html(title)));
+        }
+        for (i, line) in self.data.iter().enumerate() {
+            let head = i == 0 && self.header;
+            if head {
+                content.push("  <thead>".to_string());
+            } else if i == 0 || (i == 1 && self.header) {
+                content.push("  <tbody>".to_string());
+            }
+            content.push("    <tr>".to_string());
+            for (j, cell) in line.iter().enumerate() {
+                if cell.covered {
+                    continue;
+                }
+                let tag = if head || cell.style == CellStyle::Header {
+                    "th"
+                } else {
+                    "td"
+                };
+                let mut attributes = String::new();
+                if cell.colspan > 1 {
+                    attributes.push_str(&format!(" colspan=\"{}\"", cell.colspan));
+                }
+                if cell.rowspan > 1 {
+                    attributes.push_str(&format!(" rowspan=\"{}\"", cell.rowspan));
+                }
+                let align = match self.cell_align(j, i) {
+                    Align::Left => None,
+                    Align::Center => Some("center"),
+                    Align::Right | Align::Decimal => Some("right"),
+                };
+                if let Some(align) = align {
+                    attributes.push_str(&format!(" style=\"text-align: {}\"", align));
+                }
+                content.push(format!(
+                    "      <{tag}{attributes}>{}</{tag}>",
+                    escape_html(&cell.text)
+                ));
+            }
+            content.push("    </tr>".to_string());
+            if head {
+                content.push("  </thead>".to_string());
+            }
+        }
+        if self.data.len() > usize::from(self.header) {
+            content.push("  </tbody>".to_string());
+        }
+        content.push("</table>".to_string());
+
+        let mut result = content.join("\n");
+        result.push('\n');
+        result
+    }
+
+    // 网格表格的画布，每个位置一个显示宽度，同时返回每个位置是否是边框
+    fn grid_canvas(&self) -> (Vec<Vec<String>>, Vec<Vec<bool>>) {
+        let cell_widths = self.cell_line_widths(true);
+        // 每一列左边框的位置，最后一个是整个表格的右边框
+        let mut xs = vec![0];
+        for w in cell_widths.iter() {
+            xs.push(xs[xs.len() - 1] + w + 3);
         }
-        // 添加表格对齐
-        // 检测是否需要添加
-        let line: &String = content.get(1).unwrap();
-        if !line.contains("-") {
-            let mut nline = String::new();
-            for (i, w) in cell_widths.iter().enumerate() {
-                if style == MarkdownStyle::Normal || i != 0 {
-                    nline.push_str("| ");
+        // 每一行的高度，跨行的单元格放不下时加到最后一行
+        let mut heights: Vec<usize> = self
+            .data
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .filter(|c| !c.covered && c.rowspan == 1)
+                    .map(|c| c.text.lines().count())
+                    .max()
+                    .unwrap_or(0)
+                    .max(1)
+            })
+            .collect();
+        for (y, line) in self.data.iter().enumerate() {
+            for cell in line.iter().filter(|c| !c.covered && c.rowspan > 1) {
+                let rows = cell.rowspan.min(self.h - y);
+                let have = heights[y..y + rows].iter().sum::<usize>() + rows - 1;
+                let need = cell.text.lines().count();
+                if need > have {
+                    heights[y + rows - 1] += need - have;
                 }
-                nline.push_str("-".repeat(*w).as_str());
-                nline.push(' ');
             }
-            if style == MarkdownStyle::Normal {
-                nline.push('|');
+        }
+        let mut ys = vec![0];
+        for h in heights.iter() {
+            ys.push(ys[ys.len() - 1] + h + 1);
+        }
+
+        // 每个位置一个显示宽度，宽字符后面的位置为空字符串
+        let mut canvas = vec![vec![" ".to_string(); xs[xs.len() - 1] + 1]; ys[ys.len() - 1] + 1];
+        let mut borders = vec![vec![false; xs[xs.len() - 1] + 1]; ys[ys.len() - 1] + 1];
+        for (y, line) in self.data.iter().enumerate() {
+            for (x, cell) in line.iter().enumerate() {
+                if cell.covered {
+                    continue;
+                }
+                let (left, right) = (xs[x], xs[x + cell.colspan]);
+                let (top, bottom) = (ys[y], ys[y + cell.rowspan.min(self.h - y)]);
+                // 表头下方使用 =
+                for row in [top, bottom] {
+                    let edge = if self.header && row == ys[1] {
+                        "="
+                    } else {
+                        "-"
+                    };
+                    for c in canvas[row][left + 1..right].iter_mut() {
+                        if c != "+" {
+                            *c = edge.to_string();
+                        }
+                    }
+                    borders[row][left..=right].fill(true);
+                }
+                for row in top + 1..bottom {
+                    for col in [left, right] {
+                        canvas[row][col] = "|".to_string();
+                        borders[row][col] = true;
+                    }
+                }
+                for (row, col) in [(top, left), (top, right), (bottom, left), (bottom, right)] {
+                    canvas[row][col] = "+".to_string();
+                }
+                for (k, text) in cell.text.lines().enumerate() {
+                    let padded = self.pad_text(x, y, text, right - left - 3);
+                    let len = text_width(&padded);
+                    let row = &mut canvas[top + 1 + k];
+                    for c in row.iter_mut().skip(left + 3).take(len.saturating_sub(1)) {
+                        c.clear();
+                    }
+                    row[left + 2] = padded;
+                }
+            }
+        }
+        (canvas, borders)
+    }
+
+    /// reStructuredText 网格表格，支持合并单元格和多行单元格
+    pub fn to_rst_grid_table(&self) -> String {
+        let (canvas, _) = self.wrapped(true).grid_canvas();
+        let mut result = String::new();
+        for row in canvas.iter() {
+            result.push_str(row.concat().trim_end());
+            result.push('\n');
+        }
+        result
+    }
+
+    /// 制表符表格，和网格表格的布局相同，宽字符按 cn_length 计算宽度
+    pub fn to_box_table(&self, border: BoxBorder) -> String {
+        let (mut canvas, borders) = self.wrapped(true).grid_canvas();
+        let is = |row: usize, col: usize, symbols: &[&str]| {
+            borders[row][col] && symbols.contains(&canvas[row][col].as_str())
+        };
+        let mut glyphs = Vec::new();
+        for (row, line) in borders.iter().enumerate() {
+            for (col, border_at) in line.iter().enumerate() {
+                if !*border_at {
+                    continue;
+                }
+                let up = row > 0 && is(row - 1, col, &["|", "+"]);
+                let down = row + 1 < borders.len() && is(row + 1, col, &["|", "+"]);
+                let left = col > 0 && is(row, col - 1, &["-", "=", "+"]);
+                let right = col + 1 < line.len() && is(row, col + 1, &["-", "=", "+"]);
+                // 表头下方的横线
+                let header = canvas[row][col] == "="
+                    || (left && canvas[row][col - 1] == "=")
+                    || (right && canvas[row][col + 1] == "=");
+                glyphs.push((row, col, box_glyph(border, header, up, down, left, right)));
+            }
+        }
+        for (row, col, glyph) in glyphs {
+            canvas[row][col] = glyph.to_string();
+        }
+
+        let mut result = String::new();
+        for row in canvas.iter() {
+            result.push_str(row.concat().trim_end());
+            result.push('\n');
+        }
+        result
+    }
+
+    /// reStructuredText 简单表格，只支持跨列，单元格中的换行会变为空格
+    pub fn to_rst_simple_table(&self) -> String {
+        if self
+            .data
+            .iter()
+            .flatten()
+            .any(|c| c.rowspan > 1 || c.text.contains('\n'))
+        {
+            log::warn!("rst simple tables do not support row spans or multi-line cells");
+        }
+        // 简单表格不能折行，多行单元格合并为一行，除最后一列外的列宽按完整内容计算，
+        // 否则内容会越过下一列的起点，解析时被拆开
+        let mut data = self.clone();
+        for cell in data.data.iter_mut().flatten() {
+            cell.text = cell.text.replace('\n', " ");
+        }
+        let mut cell_widths = data.column_widths(true, usize::MAX);
+        if let (Some(last), Some(capped)) =
+            (cell_widths.last_mut(), data.cell_line_widths(true).last())
+        {
+            *last = *capped;
+        }
+        let border: Vec<String> = cell_widths.iter().map(|w| "=".repeat(*w)).collect();
+        let border = border.join("  ");
+
+        let mut content: Vec<String> = vec![border.clone()];
+        for (i, line) in data.data.iter().enumerate() {
+            let mut cells: Vec<String> = Vec::new();
+            let mut dashes: Vec<String> = Vec::new();
+            for (j, cell) in line.iter().enumerate() {
+                // 被上方跨行单元格覆盖的位置留空，被左侧跨列单元格覆盖的跳过
+                if cell.covered {
+                    if data.merge_origin(j, i).is_some_and(|(_, y)| y < i) {
+                        cells.push(" ".repeat(cell_widths[j]));
+                        dashes.push("-".repeat(cell_widths[j]));
+                    }
+                    continue;
+                }
+                let width = Self::span_width(&cell_widths, j, cell.colspan, 2);
+                cells.push(data.pad_text(j, i, &cell.text, width));
+                dashes.push("-".repeat(width));
+            }
+            content.push(cells.join("  "));
+            // 跨列的单元格下方用 - 标记
+            if line.iter().any(|c| !c.covered && c.colspan > 1) {
+                content.push(dashes.join("  "));
             }
-            content.insert(1, nline);
+            if i == 0 && self.header {
+                content.push(border.clone());
+            }
+        }
+        content.push(border);
+
+        let mut result = String::new();
+        for x in content.iter() {
+            result.push_str(x.trim_end());
+            result.push('\n');
+        }
+        result
+    }
+
+    /// org 表格，保留分隔线、对齐标记行和公式行
+    pub fn to_org_table(&self) -> String {
+        if self.has_merged_cells() {
+            log::warn!("org tables do not support merged cells, spans are dropped");
+        }
+        let cookies: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| cookie(c.align, c.max_width))
+            .collect();
+        // org 单元格不能换行也不折行，列宽按合并成一行后的完整内容计算
+        let texts: Vec<Vec<String>> = self
+            .data
+            .iter()
+            .map(|line| line.iter().map(|c| c.text.replace('\n', " ")).collect())
+            .collect();
+        let mut cell_widths = self.column_widths(false, usize::MAX);
+        for (j, w) in cell_widths.iter_mut().enumerate() {
+            *w = texts
+                .iter()
+                .map(|line| text_width(&line[j]))
+                .fold(cmp::max(*w, cookies[j].len()), cmp::max);
+        }
+
+        let org_line = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+        let hline: Vec<String> = cell_widths.iter().map(|w| "-".repeat(*w)).collect();
+        let hline = format!("|-{}-|", hline.join("-+-"));
+        let mut rules = self.rules.clone();
+        if self.header && !rules.contains(&1) {
+            rules.push(1);
+        }
+
+        let mut content: Vec<String> = Vec::new();
+        if let Some(title) = self.title.strip_prefix('.') {
+            content.push(format!("#+CAPTION: {}", title));
+        }
+        // 对齐标记行放在表头下方
+        let cookie_row = if cookies.iter().all(|c| c.is_empty()) {
+            None
+        } else if self.header {
+            Some(1)
         } else {
-            let mut nline = String::new();
-            let cells = self.data.get(1).unwrap();
-            for (w, cell) in cells.iter().enumerate() {
-                if style == MarkdownStyle::Normal || w != 0 {
-                    nline.push_str("| ");
-                }
-                if cell.starts_with(":-") && cell.ends_with("-:") {
-                    nline.push(':');
-                    nline.push_str("-".repeat(cell_widths[w] - 2).as_str());
-                    nline.push(':');
-                } else if cell.starts_with(":-") {
-                    nline.push(':');
-                    nline.push_str("-".repeat(cell_widths[w] - 1).as_str());
-                } else if cell.starts_with(":-") {
-                    nline.push_str("-".repeat(cell_widths[w] - 1).as_str());
-                    nline.push(':');
+            Some(0)
+        };
+        for i in 0..=self.data.len() {
+            for _ in rules.iter().filter(|r| **r == i) {
+                content.push(hline.clone());
+            }
+            if cookie_row == Some(i) {
+                let cells = (0..self.w)
+                    .map(|j| format!("{:<w$}", cookies[j], w = cell_widths[j]))
+                    .collect();
+                content.push(org_line(cells));
+            }
+            if let Some(line) = texts.get(i) {
+                let cells = line
+                    .iter()
+                    .enumerate()
+                    .map(|(j, text)| self.pad_text(j, i, text, cell_widths[j]))
+                    .collect();
+                content.push(org_line(cells));
+            }
+        }
+        content.extend(self.formulas.iter().cloned());
+
+        let mut result = String::new();
+        for x in content.iter() {
+            result.push_str(x.trim_end());
+            result.push('\n');
+        }
+        result
+    }
+
+    /// LaTeX booktabs 表格，有标题时放在 table 环境中
+    ///
+    /// 跨列使用 `\multicolumn`，跨行使用 multirow 宏包的 `\multirow`
+    pub fn to_latex_table(&self) -> String {
+        let escaped: Vec<Vec<String>> = self
+            .data
+            .iter()
+            .map(|line| line.iter().map(|c| escape_latex(&c.text)).collect())
+            .collect();
+        let mut cell_widths = vec![0; self.w];
+        for line in self.data.iter().zip(escaped.iter()) {
+            for (j, (cell, text)) in line.0.iter().zip(line.1.iter()).enumerate() {
+                if !cell.is_merged() {
+                    cell_widths[j] = cmp::max(cell_widths[j], text_width(text));
+                }
+            }
+        }
+
+        let mut rows: Vec<String> = Vec::new();
+        for (i, line) in self.data.iter().enumerate() {
+            let mut cells: Vec<String> = Vec::new();
+            for (j, cell) in line.iter().enumerate() {
+                if cell.covered {
+                    // 跨行覆盖的位置留空，跨列覆盖的位置跳过
+                    if self.merge_origin(j, i).is_some_and(|(_, y)| y < i) {
+                        cells.push(" ".repeat(cell_widths[j]));
+                    }
+                    continue;
+                }
+                let mut text = escaped[i][j].clone();
+                if cell.rowspan > 1 {
+                    text = format!("\\multirow{{{}}}{{*}}{{{}}}", cell.rowspan, text);
+                }
+                if cell.colspan > 1 {
+                    let spec = column_spec(self.cell_align(j, i));
+                    text = format!("\\multicolumn{{{}}}{{{}}}{{{}}}", cell.colspan, spec, text);
+                    cells.push(text);
                 } else {
-                    nline.push_str("-".repeat(cell_widths[w]).as_str());
+                    cells.push(self.pad_text(j, i, &text, cell_widths[j]));
                 }
-                nline.push(' ');
             }
-            if style == MarkdownStyle::Normal {
-                nline.push('|');
+            rows.push(format!("{} \\\\", cells.join(" & ").trim_end()));
+        }
+
+        let spec: String = (0..self.w).map(|x| column_spec(self.align(x))).collect();
+        let mut content: Vec<String> = vec![format!("\\begin{{tabular}}{{{}}}", spec)];
+        content.push("  \\toprule".to_string());
+        for (i, row) in rows.into_iter().enumerate() {
+            content.push(format!("  {}", row));
+            if i == 0 && self.header {
+                content.push("  \\midrule".to_string());
             }
-            content.insert(1, nline.trim().to_string());
-            content.remove(2);
         }
+        content.push("  \\bottomrule".to_string());
+        content.push("\\end{tabular}".to_string());
+
+        // 标题需要 table 环境
+        if let Some(title) = self.title.strip_prefix('.') {
+            let mut table = vec![
+                "\\begin{table}".to_string(),
+                "  \\centering".to_string(),
+                format!("  \\caption{{{}}}", escape_latex(title)),
+            ];
+            table.extend(content.into_iter().map(|line| format!("  {}", line)));
+            table.push("\\end{table}".to_string());
+            content = table;
+        }
+
+        let mut result = content.join("\n");
+        result.push('\n');
+        result
+    }
+
+    /// mediawiki 表格，换行使用 `<br>`
+    pub fn to_mediawiki_table(&self) -> String {
+        let mut content: Vec<String> = vec!["{| class=\"wikitable\"".to_string()];
+        if let Some(title) = self.title.strip_prefix('.') {
+            content.push(format!("|+ {}", title));
+        }
+        for (i, line) in self.data.iter().enumerate() {
+            content.push("|-".to_string());
+            let mut cells: Vec<(bool, String)> = Vec::new();
+            for (j, cell) in line.iter().enumerate() {
+                if cell.covered {
+                    continue;
+                }
+                let mut attributes: Vec<String> = Vec::new();
+                if cell.colspan > 1 {
+                    attributes.push(format!("colspan=\"{}\"", cell.colspan));
+                }
+                if cell.rowspan > 1 {
+                    attributes.push(format!("rowspan=\"{}\"", cell.rowspan));
+                }
+                match self.cell_align(j, i) {
+                    Align::Left => {}
+                    Align::Center => attributes.push("style=\"text-align: center\"".to_string()),
+                    Align::Right | Align::Decimal => {
+                        attributes.push("style=\"text-align: right\"".to_string())
+                    }
+                }
+                let text = cell.text.replace('\n', "<br>");
+                let text = if attributes.is_empty() {
+                    text
+                } else {
+                    format!("{} | {}", attributes.join(" "), text)
+                };
+                let th = (i == 0 && self.header) || cell.style == CellStyle::Header;
+                cells.push((th, text));
+            }
+            // 同一行的单元格类型相同时写在一行
+            if cells.iter().all(|c| c.0) {
+                let texts: Vec<&str> = cells.iter().map(|c| c.1.as_str()).collect();
+                content.push(format!("! {}", texts.join(" !! ")));
+            } else if cells.iter().all(|c| !c.0) {
+                let texts: Vec<&str> = cells.iter().map(|c| c.1.as_str()).collect();
+                content.push(format!("| {}", texts.join(" || ")));
+            } else {
+                for (th, text) in cells.iter() {
+                    content.push(format!("{} {}", if *th { '!' } else { '|' }, text));
+                }
+            }
+        }
+        content.push("|}".to_string());
 
         let mut result = String::new();
         for x in content.iter() {
-            result.push_str(x.trim());
+            result.push_str(x.trim_end());
+            result.push('\n');
+        }
+        result
+    }
+
+    /// jira/confluence 表格，表头使用 `||`，换行使用 `\\`
+    pub fn to_jira_table(&self) -> String {
+        if self.has_merged_cells() {
+            log::warn!("jira tables do not support merged cells, spans are dropped");
+        }
+        // 换行写成 \\，列宽按合并成一行后的完整内容计算
+        let texts: Vec<Vec<String>> = self
+            .data
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|c| c.text.replace('\n', " \\\\ "))
+                    .collect()
+            })
+            .collect();
+        let cell_widths: Vec<usize> = self
+            .column_widths(false, usize::MAX)
+            .into_iter()
+            .enumerate()
+            .map(|(j, w)| {
+                texts
+                    .iter()
+                    .map(|line| text_width(&line[j]))
+                    .fold(w, cmp::max)
+            })
+            .collect();
+        let mut result = String::new();
+        for (i, line) in texts.iter().enumerate() {
+            let sep = if i == 0 && self.header { "||" } else { "|" };
+            let cells: Vec<String> = line
+                .iter()
+                .enumerate()
+                .map(|(j, text)| self.pad_text(j, i, text, cell_widths[j]))
+                .collect();
+            let line = format!("{sep} {} {sep}", cells.join(&format!(" {sep} ")));
+            result.push_str(line.trim_end());
             result.push('\n');
         }
         result
     }
 
     pub fn to_asciidoc_table(&self) -> String {
-        let cell_widths = self.cell_line_widths(false);
+        let cell_widths = self.cell_line_widths(true);
 
         let mut content: Vec<String> = Vec::new();
         // 第 i 行
-        for line in self.data.iter() {
+        for (i, line) in self.data.iter().enumerate() {
             let mut xline = String::new();
             for (j, cell) in line.iter().enumerate() {
-                let symbol = if j == 0 { "| " } else { " | " };
-                let (v1, v2) = (cell_widths[j], utils::cn_length(cell));
-                let blank = " ".repeat(cmp::max(v1, v2) - cmp::min(v1, v2));
-                xline.push_str(symbol);
-                xline.push_str(cell);
-                xline.push_str(blank.as_str());
+                if cell.covered {
+                    continue;
+                }
+                if j != 0 {
+                    xline.push(' ');
+                }
+                xline.push_str(&cell.asciidoc_spec());
+                xline.push_str("| ");
+                let width = Self::span_width(&cell_widths, j, cell.colspan, 3);
+                xline.push_str(&self.pad_cell(j, i, width));
+            }
+            content.push(xline.trim_end().to_string());
+            // 表头后空一行
+            if i == 0 && self.header {
+                content.push(String::new());
             }
-            content.push(xline.clone());
         }
 
         // 添加表头和表尾
@@ -254,6 +1356,28 @@ impl TableData {
         content.insert(0, border.clone());
         content.push(border);
 
+        // 列设置没有变化时保留原文的 cols，否则在原来的位置重新生成，
+        // 原文没有 cols 时只在存在非默认的列设置时添加到最后
+        let mut attributes = self.attributes.clone();
+        let cols: Vec<String> = self.columns.iter().map(|c| c.to_asciidoc()).collect();
+        let cols = format!("cols=\"{}\"", cols.join(","));
+        let default = ColumnSpec::default().to_asciidoc();
+        let index = attributes
+            .iter()
+            .position(|attr| cols_value(attr).is_some());
+        let original = index.and_then(|i| cols_value(&attributes[i]).map(parse_cols));
+        match index {
+            Some(i) if original.as_ref() != Some(&self.columns) => attributes[i] = cols,
+            Some(_) => {}
+            None if self.columns.iter().any(|c| c.to_asciidoc() != default) => {
+                attributes.push(cols);
+            }
+            None => {}
+        }
+        if !attributes.is_empty() {
+            content.insert(0, format!("[{}]", attributes.join(",")));
+        }
+
         // 添加标题
         if self.title.len() > 0 {
             content.insert(0, self.title.clone() + "\n");
@@ -261,7 +1385,7 @@ impl TableData {
 
         let mut result = String::new();
         for x in content.iter() {
-            result.push_str(x.trim());
+            result.push_str(x.trim_end());
             result.push('\n');
         }
 
@@ -277,6 +1401,17 @@ mod tests {
         let _ = env_logger::builder().is_test(true).try_init();
     }
 
+    // 按行给出单元格内容创建表格
+    fn table(rows: &[&[&str]]) -> TableData {
+        let mut data = TableData::new(rows[0].len(), rows.len(), 40, 99);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, v) in row.iter().enumerate() {
+                data.set_cell(x, y, v);
+            }
+        }
+        data
+    }
+
     #[test]
     fn test_data_new() {
         init();
@@ -289,6 +1424,262 @@ mod tests {
         log::debug!("test data: \n{:#?}", data)
     }
 
+    #[test]
+    fn test_merge() {
+        init();
+        let mut data = table(&[
+            &["a", "b", "c"],
+            &["wide cell text", "", "x"],
+            &["1", "2", "3"],
+        ]);
+        assert!(data.merge(0, 1, 2, 1));
+        assert!(data.has_merged_cells());
+        assert_eq!(data.merge_origin(1, 1), Some((0, 1)));
+        // 部分重叠和越界都不能合并
+        assert!(!data.merge(1, 0, 2, 2));
+        assert!(!data.merge(2, 2, 2, 1));
+
+        assert_eq!(
+            data.to_asciidoc_table(),
+            "|=====================\n\
+             | a      | b     | c\n\
+             2+| wide cell text | x\n\
+             | 1      | 2     | 3\n\
+             |=====================\n"
+        );
+        // markdown 退化为普通单元格
+        assert_eq!(
+            data.to_normal_markdown_table(),
+            "| a              | b   | c   |\n\
+             | -------------- | --- | --- |\n\
+             | wide cell text |     | x   |\n\
+             | 1              | 2   | 3   |\n"
+        );
+
+        assert!(data.unmerge(1, 1));
+        assert!(!data.unmerge(1, 1));
+        assert!(!data.has_merged_cells());
+        assert_eq!(data.cell(0, 1), Some("wide cell text"));
+    }
+
+    #[test]
+    fn test_rst() {
+        init();
+        let mut data = table(&[&["a", "b"], &["wide", ""], &["x\ny", "z"]]);
+        data.header = true;
+        data.merge(0, 1, 2, 1);
+        data.set_align(1, Align::Right);
+        assert_eq!(
+            data.to_rst_grid_table(),
+            "+-----+-----+\n\
+             | a   |   b |\n\
+             +=====+=====+\n\
+             | wide      |\n\
+             +-----+-----+\n\
+             | x   |   z |\n\
+             | y   |     |\n\
+             +-----+-----+\n"
+        );
+        assert_eq!(
+            data.to_rst_simple_table(),
+            "===  ===\n\
+             a      b\n\
+             ===  ===\n\
+             wide\n\
+             --------\n\
+             x y    z\n\
+             ===  ===\n"
+        );
+    }
+
+    #[test]
+    fn test_box() {
+        init();
+        let mut data = table(&[&["名字", "b"], &["wide", ""], &["x\ny", "中文"]]);
+        data.header = true;
+        data.merge(0, 1, 2, 1);
+        assert_eq!(
+            data.to_box_table(BoxBorder::Single),
+            "┌──────┬──────┐\n\
+             │ 名字 │ b    │\n\
+             ╞══════╧══════╡\n\
+             │ wide        │\n\
+             ├──────┬──────┤\n\
+             │ x    │ 中文 │\n\
+             │ y    │      │\n\
+             └──────┴──────┘\n"
+        );
+        let heavy = data.to_box_table(BoxBorder::Heavy);
+        assert!(heavy.starts_with("┏━━━━━━┳━━━━━━┓\n┃ 名字 ┃ b    ┃\n┣━━━━━━┻━━━━━━┫"));
+        let ascii = data.to_box_table(BoxBorder::Ascii);
+        assert_eq!(ascii, data.to_rst_grid_table());
+    }
+
+    #[test]
+    fn test_wrap() {
+        init();
+        let mut data = table(&[&["name", "note"], &["a", "a long note 很长的中文说明"]]);
+        data.cell_max_w = 10;
+        data.header = true;
+        assert_eq!(
+            data.to_rst_grid_table(),
+            "+------+------------+\n\
+             | name | note       |\n\
+             +======+============+\n\
+             | a    | a long     |\n\
+             |      | note 很长  |\n\
+             |      | 的中文说明 |\n\
+             +------+------------+\n"
+        );
+        // 超出行宽时按比例缩小各列，每一行都不超过行宽
+        let mut data = table(&[&["a", "b"], &["one two three", "four five six seven eight"]]);
+        data.line_max_w = 30;
+        for table in [
+            data.to_rst_grid_table(),
+            data.to_box_table(BoxBorder::Single),
+        ] {
+            assert!(table.lines().all(|l| l.chars().count() <= 30), "{}", table);
+        }
+        // markdown 按分配的列宽折行后使用 <br> 连接，折出的每一段加上边框不超过行宽
+        let markdown = data.to_normal_markdown_table();
+        assert_eq!(
+            markdown.lines().nth(2).unwrap(),
+            "| one two<br>three | four five six<br>seven eight |"
+        );
+        let segments: usize = (0..2)
+            .map(|j| {
+                markdown
+                    .lines()
+                    .filter(|l| !l.contains("---"))
+                    .flat_map(|l| l.split('|').nth(j + 1).unwrap().split("<br>"))
+                    .map(|t| utils::cn_length(t.trim()))
+                    .max()
+                    .unwrap()
+            })
+            .sum();
+        assert!(segments + 3 * 2 + 1 <= 30, "{}", markdown);
+        // 多行内容使用 <br> 连接，所有行的列宽相同
+        data.set_cell(0, 1, "one");
+        data.set_cell(1, 1, "four\nfive");
+        let markdown = data.to_normal_markdown_table();
+        assert_eq!(
+            markdown,
+            "| a   | b            |\n\
+             | --- | ------------ |\n\
+             | one | four<br>five |\n"
+        );
+        let columns = |l: &str| -> Vec<usize> {
+            l.char_indices()
+                .filter(|(_, c)| *c == '|')
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert!(lines.iter().all(|l| columns(l) == columns(lines[0])));
+        assert!(lines.iter().all(|l| utils::cn_length(l) <= 30));
+    }
+
+    #[test]
+    fn test_edit() {
+        init();
+        let mut data = table(&[
+            &["h1", "h2", "h3"],
+            &["wide", "", "x"],
+            &["tall", "p", "q"],
+            &["", "r", "s"],
+        ]);
+        data.header = true;
+        data.merge(0, 1, 2, 1);
+        data.merge(0, 2, 1, 2);
+
+        // 插入到合并单元格中间时合并单元格变大
+        assert!(data.insert_row(3));
+        assert_eq!(data.cell_at(0, 2).unwrap().rowspan, 3);
+        assert!(data.cell_at(0, 3).unwrap().covered);
+        assert!(data.insert_column(1));
+        assert_eq!(data.cell_at(0, 1).unwrap().colspan, 3);
+        assert!(data.cell_at(1, 1).unwrap().covered);
+        // 删除合并单元格的第一行或者第一列时内容移到下一行或者下一列
+        assert!(data.delete_row(2));
+        assert_eq!(
+            (data.cell(0, 2), data.cell_at(0, 2).unwrap().rowspan),
+            (Some("tall"), 2)
+        );
+        assert!(data.delete_column(0));
+        assert_eq!(
+            (data.cell(0, 1), data.cell_at(0, 1).unwrap().colspan),
+            (Some("wide"), 2)
+        );
+        assert_eq!((data.w, data.h), (3, 4));
+        assert_eq!(data.cell(1, 3), Some("r"));
+
+        // 表头不能移动，穿过合并单元格的移动无效
+        assert!(!data.move_row(0, 1));
+        assert!(!data.move_column(0, 2));
+        assert!(data.move_row(3, 1));
+        assert_eq!(data.cell(2, 1), Some("s"));
+        assert!(data.unmerge(0, 2));
+        assert!(data.move_column(2, 0));
+        assert_eq!(data.cell(0, 0), Some("h3"));
+
+        assert!(data.merge(1, 2, 2, 1));
+        data.transpose();
+        assert_eq!((data.w, data.h), (4, 3));
+        assert_eq!(data.cell_at(2, 1).unwrap().rowspan, 2);
+        assert_eq!(data.cell(2, 1), Some("wide"));
+        assert_eq!(data.cell(1, 0), Some("s"));
+    }
+
+    #[test]
+    fn test_sort() {
+        init();
+        let mut data = table(&[
+            &["name", "n"],
+            &["b", "x10"],
+            &["a", "X2"],
+            &["c", "x10"],
+            &["a", "X2"],
+            &["d", "x1"],
+        ]);
+        data.header = true;
+        assert!(data.sort_by_column(1, SortMode::Natural, true));
+        let names: Vec<&str> = (0..data.h).map(|y| data.cell(0, y).unwrap()).collect();
+        assert_eq!(names, vec!["name", "b", "c", "a", "a", "d"]);
+        assert_eq!(data.dedup_rows(), 1);
+        assert_eq!(data.h, 5);
+        assert_eq!(data.dedup_rows(), 0);
+    }
+
+    #[test]
+    fn test_latex() {
+        init();
+        let mut data = table(&[
+            &["item", "cost", "note"],
+            &["a_1", "5", "50%"],
+            &["total", "", "x"],
+        ]);
+        data.header = true;
+        data.set_align(1, Align::Right);
+        assert_eq!(
+            data.to_latex_table(),
+            "\\begin{tabular}{lrl}\n\
+             \x20 \\toprule\n\
+             \x20 item  & cost & note \\\\\n\
+             \x20 \\midrule\n\
+             \x20 a\\_1  &    5 & 50\\% \\\\\n\
+             \x20 total &      & x \\\\\n\
+             \x20 \\bottomrule\n\
+             \\end{tabular}\n"
+        );
+
+        data.title = ".Costs".to_string();
+        data.merge(0, 2, 2, 1);
+        let latex = data.to_latex_table();
+        assert!(latex.starts_with("\\begin{table}\n  \\centering\n  \\caption{Costs}\n"));
+        assert!(latex.contains("    \\multicolumn{2}{l}{total} & x \\\\\n"));
+        assert!(latex.ends_with("  \\end{tabular}\n\\end{table}\n"));
+    }
+
     #[test]
     fn test_str() {
         init();
@@ -0,0 +1,110 @@
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// 按列排序时比较单元格的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortMode {
+    /// 按字符串比较
+    #[default]
+    Lexical,
+    /// 按数值比较，不是数字的排在后面
+    Numeric,
+    /// 自然排序，`a2` 排在 `a10` 前面，忽略大小写
+    Natural,
+}
+
+/// 表格编辑操作，行列序号从 0 开始，表头所在的行不参与行的操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableOp {
+    InsertRow(usize),
+    DeleteRow(usize),
+    /// 把第一个位置的行移动到第二个位置
+    MoveRow(usize, usize),
+    InsertColumn(usize),
+    DeleteColumn(usize),
+    MoveColumn(usize, usize),
+    Transpose,
+    Sort {
+        column: usize,
+        mode: SortMode,
+        descending: bool,
+    },
+    /// 删除重复的行，保留第一次出现的行
+    Dedup,
+}
+
+// 数字可以带有千分位和百分号，例如 1,234.5%
+fn parse_number(s: &str) -> Option<f64> {
+    let s = s.trim().trim_end_matches('%').replace(',', "");
+    s.parse().ok()
+}
+
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        digits.push(c);
+    }
+    digits
+}
+
+// 连续的数字按数值比较，其余字符忽略大小写逐个比较
+fn natural_compare(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        let ordering = match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (da, db) = (take_digits(&mut a), take_digits(&mut b));
+                let (ta, tb) = (da.trim_start_matches('0'), db.trim_start_matches('0'));
+                ta.len().cmp(&tb.len()).then_with(|| ta.cmp(tb))
+            }
+            (Some(x), Some(y)) => {
+                a.next();
+                b.next();
+                x.to_lowercase().cmp(y.to_lowercase())
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// 按排序方式比较两个单元格
+pub fn compare(a: &str, b: &str, mode: SortMode) -> Ordering {
+    match mode {
+        SortMode::Lexical => a.cmp(b),
+        SortMode::Numeric => match (parse_number(a), parse_number(b)) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.cmp(b),
+        },
+        SortMode::Natural => natural_compare(a, b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(compare("10", "9", SortMode::Lexical), Ordering::Less);
+        assert_eq!(compare("10", "9", SortMode::Numeric), Ordering::Greater);
+        assert_eq!(
+            compare("1,200", "15%", SortMode::Numeric),
+            Ordering::Greater
+        );
+        assert_eq!(compare("n/a", "3", SortMode::Numeric), Ordering::Greater);
+        assert_eq!(
+            compare("file10", "File2", SortMode::Natural),
+            Ordering::Greater
+        );
+        assert_eq!(compare("a007", "a7", SortMode::Natural), Ordering::Equal);
+        assert_eq!(compare("v1.2", "v1.10", SortMode::Natural), Ordering::Less);
+    }
+}
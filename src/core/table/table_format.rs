@@ -1,10 +1,78 @@
 use super::TableData;
+use super::table_asciidoc::parse_asciidoc;
+use super::table_csv::{detect_delimiter, parse_delimited};
+use super::table_data::Align;
+use super::table_edit::TableOp;
+use super::table_html::{is_html_table, parse_html};
+use super::table_org::{is_org_table, parse_org};
+use super::table_rst::{is_rst_grid, is_rst_simple, parse_rst_grid, parse_rst_simple};
+use super::table_wiki::{is_jira, is_mediawiki, parse_jira, parse_mediawiki};
+use crate::core::BoxBorder;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TableMode {
     Asciidoc,
     Markdown,
     MarkdownGFM,
+    Csv,
+    Tsv,
+    Html,
+    RstGrid,
+    RstSimple,
+    Org,
+    Latex,
+    MediaWiki,
+    Jira,
+    BoxDrawing(BoxBorder),
+}
+
+impl TableMode {
+    /// 命令行参数中使用的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            TableMode::Asciidoc => "asciidoc",
+            TableMode::Markdown => "markdown",
+            TableMode::MarkdownGFM => "gfm",
+            TableMode::Csv => "csv",
+            TableMode::Tsv => "tsv",
+            TableMode::Html => "html",
+            TableMode::RstGrid => "rst-grid",
+            TableMode::RstSimple => "rst-simple",
+            TableMode::Org => "org",
+            TableMode::Latex => "latex",
+            TableMode::MediaWiki => "mediawiki",
+            TableMode::Jira => "jira",
+            TableMode::BoxDrawing(border) => match border {
+                BoxBorder::Single => "box",
+                BoxBorder::Double => "box-double",
+                BoxBorder::Rounded => "box-rounded",
+                BoxBorder::Heavy => "box-heavy",
+                BoxBorder::Ascii => "box-ascii",
+            },
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "asciidoc" | "adoc" => Some(TableMode::Asciidoc),
+            "markdown" | "md" => Some(TableMode::Markdown),
+            "gfm" | "markdowngfm" => Some(TableMode::MarkdownGFM),
+            "csv" => Some(TableMode::Csv),
+            "tsv" => Some(TableMode::Tsv),
+            "html" => Some(TableMode::Html),
+            "rst" | "rst-grid" | "rstgrid" => Some(TableMode::RstGrid),
+            "rst-simple" | "rstsimple" => Some(TableMode::RstSimple),
+            "org" => Some(TableMode::Org),
+            "latex" | "tex" => Some(TableMode::Latex),
+            "mediawiki" | "wiki" => Some(TableMode::MediaWiki),
+            "jira" | "confluence" => Some(TableMode::Jira),
+            "box" => Some(TableMode::BoxDrawing(BoxBorder::Single)),
+            other => other
+                .strip_prefix("box-")
+                .and_then(BoxBorder::from_name)
+                .map(TableMode::BoxDrawing),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -17,7 +85,13 @@ pub enum MarkdownStyle {
 pub enum OriginTableMode {
     Asciidoc,
     Markdown,
-    NoneByTab,
+    /// csv、tsv 等带分隔符的表格
+    Delimited(char),
+    RstGrid,
+    RstSimple,
+    Org,
+    MediaWiki,
+    Jira,
     NoneBySpace,
     None,
 }
@@ -54,10 +128,34 @@ impl TableFormator {
     }
 
     fn check_origin_table_mode(&self, text: &str) -> OriginTableMode {
+        // reStructuredText 表格的第一行是边框
+        let first = text
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or_default();
+        if is_rst_grid(first) {
+            return OriginTableMode::RstGrid;
+        }
+        if is_rst_simple(first) {
+            return OriginTableMode::RstSimple;
+        }
+        if is_mediawiki(first) {
+            return OriginTableMode::MediaWiki;
+        }
+        if is_jira(first) {
+            return OriginTableMode::Jira;
+        }
+        // org 的分隔线和 markdown 的对齐行相似，需要先判断
+        if is_org_table(text) {
+            return OriginTableMode::Org;
+        }
         // 不包含 | 则证明是非markdown或者asciidoc表格
         if !text.contains("|") {
+            if let Some(delimiter) = detect_delimiter(text) {
+                return OriginTableMode::Delimited(delimiter);
+            }
             if self.check_contain_symbol(text, "\t") {
-                return OriginTableMode::NoneByTab;
+                return OriginTableMode::Delimited('\t');
             }
             if self.check_contain_symbol(text, " ") {
                 return OriginTableMode::NoneBySpace;
@@ -78,6 +176,13 @@ impl TableFormator {
         }
         let lines: Vec<&str> = text.split('\n').filter(|&s| !s.is_empty()).collect();
         let line = lines[0].trim();
+        // 引号中带有 | 的 csv
+        if !line.starts_with("|")
+            && let Some(delimiter) = detect_delimiter(text)
+            && delimiter != '|'
+        {
+            return OriginTableMode::Delimited(delimiter);
+        }
         // 如果刚开始是 asciidoc 的标题的话
         if line.starts_with(".") {
             return OriginTableMode::Asciidoc;
@@ -112,22 +217,14 @@ impl TableFormator {
                 }
                 return line.matches("|").count() + 1;
             }
-            OriginTableMode::Asciidoc => {
-                for line in lines.iter() {
-                    if line.starts_with(".") {
-                        continue;
-                    }
-                    if line.starts_with("|==") {
-                        continue;
-                    }
-                    return line.matches("|").count();
-                }
-                return 0;
-            }
-            OriginTableMode::NoneByTab => {
-                let line = lines[0].trim();
-                return line.matches("\t").count() + 1;
-            }
+            // 这些格式由各自的解析函数计算列数
+            OriginTableMode::Asciidoc
+            | OriginTableMode::Delimited(_)
+            | OriginTableMode::RstGrid
+            | OriginTableMode::RstSimple
+            | OriginTableMode::Org
+            | OriginTableMode::MediaWiki
+            | OriginTableMode::Jira => unreachable!(),
             OriginTableMode::NoneBySpace => {
                 let line = lines[0].trim();
                 return line.matches(" ").count() + 1;
@@ -140,59 +237,85 @@ impl TableFormator {
     }
 
     fn try_format_into_basic_table(&self, text: &str) -> Option<TableData> {
+        // 从网页复制的表格可能只有一行
+        if is_html_table(text) {
+            return parse_html(text, self.cell_max_w, self.line_max_w);
+        }
         let lines: Vec<&str> = text.split('\n').filter(|&s| !s.is_empty()).collect();
         let h = lines.len();
         if h < 2 {
             return None;
         }
         let omode = self.check_origin_table_mode(text);
+        match omode {
+            // asciidoc 的单元格可以合并和跨行，需要单独解析
+            OriginTableMode::Asciidoc => {
+                return parse_asciidoc(text, self.cell_max_w, self.line_max_w);
+            }
+            // csv 引号中可以有分隔符和换行
+            OriginTableMode::Delimited(delimiter) => {
+                return parse_delimited(text, delimiter, self.cell_max_w, self.line_max_w);
+            }
+            OriginTableMode::RstGrid => {
+                return parse_rst_grid(text, self.cell_max_w, self.line_max_w);
+            }
+            OriginTableMode::RstSimple => {
+                return parse_rst_simple(text, self.cell_max_w, self.line_max_w);
+            }
+            OriginTableMode::Org => {
+                return parse_org(text, self.cell_max_w, self.line_max_w);
+            }
+            OriginTableMode::MediaWiki => {
+                return parse_mediawiki(text, self.cell_max_w, self.line_max_w);
+            }
+            OriginTableMode::Jira => {
+                return parse_jira(text, self.cell_max_w, self.line_max_w);
+            }
+            _ => {}
+        }
         let w = self.get_table_width(&lines, &omode);
         let mut data = TableData::new(w, h, self.cell_max_w, self.line_max_w);
         match omode {
             OriginTableMode::Markdown => {
-                for (i, line) in lines.iter().enumerate() {
+                // 对齐行不算作数据行
+                let mut i = 0;
+                let mut has_aligns = false;
+                for line in lines.iter() {
+                    let mut cells: Vec<&str> = line.split("|").collect();
                     // 正常风格
                     if line.starts_with("|") {
-                        for (j, cell) in line.split("|").enumerate() {
-                            if j == 0 {
-                                continue;
-                            }
-                            data.set_cell(j - 1, i, cell);
+                        cells.remove(0);
+                        if cells.len() > w && cells.last().is_some_and(|c| c.trim().is_empty()) {
+                            cells.pop();
                         }
-                        continue;
-                    }
-                    // github 风格
-                    for (j, cell) in line.split("|").enumerate() {
-                        data.set_cell(j, i, cell);
                     }
-                }
-            }
-            OriginTableMode::Asciidoc => {
-                for (i, line) in lines.iter().enumerate() {
-                    if i == 0 && line.starts_with(".") {
-                        data.title = line.to_string().clone();
-                    }
-                    // 忽略掉表格的部分
-                    if line.starts_with("|==") {
-                        continue;
-                    }
-                    // TODO：这里需要asciidoc的语法做一点特殊分析
-                    // 例如表格扩展之类的
-                    for (j, cell) in line.split("|").enumerate() {
-                        if j == 0 {
+                    if !has_aligns && i > 0 {
+                        let aligns: Option<Vec<Align>> =
+                            cells.iter().map(|c| Align::from_markdown(c)).collect();
+                        if let Some(aligns) = aligns {
+                            for (j, align) in aligns.into_iter().enumerate() {
+                                data.set_align(j, align);
+                            }
+                            // 对齐行之前的是表头
+                            data.header = i == 1;
+                            has_aligns = true;
                             continue;
                         }
-                        data.set_cell(j - 1, i, cell);
                     }
-                }
-            }
-            OriginTableMode::NoneByTab => {
-                for (i, line) in lines.iter().enumerate() {
-                    for (j, cell) in line.split("\t").enumerate() {
-                        data.set_cell(j, i, cell);
+                    // markdown 单元格中的换行写作 <br>
+                    for (j, cell) in cells.iter().enumerate() {
+                        data.set_cell(j, i, &cell.replace("<br>", "\n"));
                     }
+                    i += 1;
                 }
             }
+            OriginTableMode::Asciidoc
+            | OriginTableMode::Delimited(_)
+            | OriginTableMode::RstGrid
+            | OriginTableMode::RstSimple
+            | OriginTableMode::Org
+            | OriginTableMode::MediaWiki
+            | OriginTableMode::Jira => unreachable!(),
             OriginTableMode::NoneBySpace => {
                 for (i, line) in lines.iter().enumerate() {
                     for (j, cell) in line.split(" ").enumerate() {
@@ -214,22 +337,296 @@ impl TableFormator {
     }
 
     pub fn do_format(&mut self, text: &str, mode: &TableMode) -> String {
-        let data = self.try_format_into_basic_table(text);
-        match data {
-            Some(v) => match mode {
-                TableMode::Markdown => {
-                    return v.to_normal_markdown_table();
-                }
-                TableMode::MarkdownGFM => {
-                    return v.to_gfm_markdown_table();
-                }
-                TableMode::Asciidoc => {
-                    return v.to_asciidoc_table();
-                }
-            },
-            None => {
-                return "error".to_string();
+        self.format(text, mode).unwrap_or("error".to_string())
+    }
+
+    /// 格式化表格，无法识别为表格时返回 None
+    pub fn format(&mut self, text: &str, mode: &TableMode) -> Option<String> {
+        let data = self.try_format_into_basic_table(text)?;
+        Some(Self::render(&data, mode))
+    }
+
+    /// 编辑表格并使用输入的格式输出，无法识别为表格或者操作无效时返回 None
+    pub fn edit(&mut self, text: &str, op: &TableOp) -> Option<String> {
+        let mut data = self.try_format_into_basic_table(text)?;
+        if !data.apply(op) {
+            return None;
+        }
+        Some(Self::render(&data, &self.origin_output_mode(text)))
+    }
+
+    // 输入的表格对应的输出格式，用空格分隔的表格使用 markdown
+    fn origin_output_mode(&self, text: &str) -> TableMode {
+        if is_html_table(text) {
+            return TableMode::Html;
+        }
+        match self.check_origin_table_mode(text) {
+            OriginTableMode::Asciidoc => TableMode::Asciidoc,
+            OriginTableMode::Markdown if !text.trim_start().starts_with('|') => {
+                TableMode::MarkdownGFM
+            }
+            OriginTableMode::Delimited('\t') => TableMode::Tsv,
+            OriginTableMode::Delimited(_) => TableMode::Csv,
+            OriginTableMode::RstGrid => TableMode::RstGrid,
+            OriginTableMode::RstSimple => TableMode::RstSimple,
+            OriginTableMode::Org => TableMode::Org,
+            OriginTableMode::MediaWiki => TableMode::MediaWiki,
+            OriginTableMode::Jira => TableMode::Jira,
+            OriginTableMode::Markdown | OriginTableMode::NoneBySpace | OriginTableMode::None => {
+                TableMode::Markdown
             }
         }
     }
+
+    fn render(data: &TableData, mode: &TableMode) -> String {
+        match mode {
+            TableMode::Markdown => data.to_normal_markdown_table(),
+            TableMode::MarkdownGFM => data.to_gfm_markdown_table(),
+            TableMode::Asciidoc => data.to_asciidoc_table(),
+            TableMode::Csv => data.to_csv_table(),
+            TableMode::Tsv => data.to_tsv_table(),
+            TableMode::Html => data.to_html_table(),
+            TableMode::RstGrid => data.to_rst_grid_table(),
+            TableMode::RstSimple => data.to_rst_simple_table(),
+            TableMode::Org => data.to_org_table(),
+            TableMode::Latex => data.to_latex_table(),
+            TableMode::MediaWiki => data.to_mediawiki_table(),
+            TableMode::Jira => data.to_jira_table(),
+            TableMode::BoxDrawing(border) => data.to_box_table(*border),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::table::SortMode;
+
+    #[test]
+    fn test_markdown_align() {
+        let mut formator = TableFormator::new(40, 99);
+        let text = "| a | bb | c | d |\n|:--|:-:|--:|---|\n| 1 | 2 | 3 | 4 |";
+        assert_eq!(
+            formator.format(text, &TableMode::Markdown).unwrap(),
+            "| a   | bb  |   c | d   |\n\
+             | --- | :-: | --: | --- |\n\
+             | 1   |  2  |   3 | 4   |\n"
+        );
+        // 对齐行不会成为 asciidoc 的数据行
+        assert_eq!(
+            formator.format(text, &TableMode::Asciidoc).unwrap(),
+            "[cols=\"<,^,>,<\"]\n\
+             |=================\n\
+             | a   | bb  |   c | d\n\
+             \n\
+             | 1   |  2  |   3 | 4\n\
+             |=================\n"
+        );
+    }
+
+    #[test]
+    fn test_asciidoc_align() {
+        let mut formator = TableFormator::new(40, 99);
+        let text = "[cols=\">,<\"]\n|===\n| 1 | a\n| 10 | bb\n|===";
+        let output = formator.format(text, &TableMode::MarkdownGFM).unwrap();
+        assert_eq!(output, "  1 | a\n--: | ---\n 10 | bb\n");
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut formator = TableFormator::new(40, 99);
+        let text = "name,city\n\"Doe, John\",\"New\nYork\"\n\"A | B\",Paris\n";
+        assert_eq!(
+            formator.format(text, &TableMode::Csv).unwrap(),
+            "name,city\n\"Doe, John\",\"New\nYork\"\nA | B,Paris\n"
+        );
+        assert_eq!(
+            formator.format(text, &TableMode::Tsv).unwrap(),
+            "name\tcity\nDoe, John\t\"New\nYork\"\nA | B\tParis\n"
+        );
+        assert_eq!(
+            formator.format("a\tb\n1\t2", &TableMode::Csv).unwrap(),
+            "a,b\n1,2\n"
+        );
+        assert_eq!(
+            formator.format("a;b\n1,5;2", &TableMode::Markdown).unwrap(),
+            "| a   | b   |\n| --- | --- |\n| 1,5 | 2   |\n"
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        let mut formator = TableFormator::new(40, 99);
+        let text = "<table><tr><th>a</th><th>b</th></tr><tr><td colspan=2>wide</td></tr></table>";
+        assert_eq!(
+            formator.format(text, &TableMode::Asciidoc).unwrap(),
+            "|========\n| a   | b\n\n2+| wide\n|========\n"
+        );
+        assert_eq!(
+            formator.format("a,b\n1,2", &TableMode::Html).unwrap(),
+            "<table>\n  <thead>\n    <tr>\n      <th>a</th>\n      <th>b</th>\n    </tr>\n  </thead>\n  \
+             <tbody>\n    <tr>\n      <td>1</td>\n      <td>2</td>\n    </tr>\n  </tbody>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn test_rst() {
+        let mut formator = TableFormator::new(40, 99);
+        let text = "| a | b |\n|---|--:|\n| 1 | 22 |";
+        let grid = formator.format(text, &TableMode::RstGrid).unwrap();
+        assert_eq!(
+            grid,
+            "+-----+-----+\n| a   |   b |\n+=====+=====+\n| 1   |  22 |\n+-----+-----+\n"
+        );
+        let simple = formator.format(text, &TableMode::RstSimple).unwrap();
+        assert_eq!(simple, "===  ===\na      b\n===  ===\n1     22\n===  ===\n");
+        // 两种格式都可以解析回来
+        for rst in [grid, simple] {
+            assert_eq!(
+                formator.format(&rst, &TableMode::Csv).unwrap(),
+                "a,b\n1,22\n"
+            );
+        }
+        // 简单表格不能折行，超过单元格宽度的内容也不能越过下一列
+        let mut formator = TableFormator::new(20, 40);
+        let text = "name,n\na very long description that should be wrapped,1";
+        let simple = formator.format(text, &TableMode::RstSimple).unwrap();
+        assert_eq!(
+            formator.format(&simple, &TableMode::Csv).unwrap(),
+            "name,n\na very long description that should be wrapped,1\n"
+        );
+    }
+
+    #[test]
+    fn test_org() {
+        let mut formator = TableFormator::new(40, 99);
+        let text = "| a | b |\n|:--|--:|\n| 1 | 22 |";
+        let org = formator.format(text, &TableMode::Org).unwrap();
+        assert_eq!(
+            org,
+            "| a   |   b |\n|-----+-----|\n|     | <r> |\n| 1   |  22 |\n"
+        );
+        assert_eq!(
+            formator.format(&org, &TableMode::MarkdownGFM).unwrap(),
+            "a   |   b\n--- | --:\n1   |  22\n"
+        );
+        // org 不折行，超过 cell_max_w 的单元格也保持对齐
+        let mut formator = TableFormator::new(5, 99);
+        let org = formator
+            .format("a,b\nlong text,1\nx,2", &TableMode::Org)
+            .unwrap();
+        assert_eq!(
+            org,
+            "| a         | b   |\n|-----------+-----|\n| long text | 1   |\n| x         | 2   |\n"
+        );
+    }
+
+    #[test]
+    fn test_wiki() {
+        let mut formator = TableFormator::new(40, 99);
+        let text = "| a | b |\n|---|--:|\n| 1 | 22 |";
+        let wiki = formator.format(text, &TableMode::MediaWiki).unwrap();
+        assert_eq!(
+            wiki,
+            "{| class=\"wikitable\"\n|-\n! a !! style=\"text-align: right\" | b\n\
+             |-\n| 1 || style=\"text-align: right\" | 22\n|}\n"
+        );
+        let jira = formator.format(text, &TableMode::Jira).unwrap();
+        assert_eq!(jira, "|| a   ||   b ||\n| 1   |  22 |\n");
+        // 多行和超过 cell_max_w 的单元格合并成一行后保持对齐
+        let mut narrow = TableFormator::new(5, 99);
+        assert_eq!(
+            narrow
+                .format("a,b\n\"long\ntext\",1\nx,2", &TableMode::Jira)
+                .unwrap(),
+            "|| a            || b   ||\n| long \\\\ text | 1   |\n| x            | 2   |\n"
+        );
+        // 两种格式都可以解析回来
+        for wiki in [wiki, jira] {
+            assert_eq!(
+                formator.format(&wiki, &TableMode::Csv).unwrap(),
+                "a,b\n1,22\n"
+            );
+        }
+        // 每个单元格单独一行时，列数按第一行的单元格计算
+        let wiki = "{|\n|-\n! a\n! b\n|-\n| 1\n| 2\n|-\n| 3\n| 4\n|}";
+        assert_eq!(
+            formator.format(wiki, &TableMode::Csv).unwrap(),
+            "a,b\n1,2\n3,4\n"
+        );
+    }
+
+    #[test]
+    fn test_edit() {
+        let mut formator = TableFormator::new(40, 99);
+        let text = "| n | v |\n|---|--:|\n| b | 10 |\n| a | 9 |\n| b | 10 |";
+        let op = TableOp::Sort {
+            column: 1,
+            mode: SortMode::Numeric,
+            descending: false,
+        };
+        assert_eq!(
+            formator.edit(text, &op).unwrap(),
+            "| n   |   v |\n\
+             | --- | --: |\n\
+             | a   |   9 |\n\
+             | b   |  10 |\n\
+             | b   |  10 |\n"
+        );
+        // 输出保持输入的格式
+        assert_eq!(
+            formator.edit("a,b\n1,2\n1,2", &TableOp::Dedup).unwrap(),
+            "a,b\n1,2\n"
+        );
+        assert_eq!(formator.edit("a,b\n1,2", &TableOp::DeleteRow(0)), None);
+    }
+
+    #[test]
+    fn test_asciidoc_spans() {
+        let mut formator = TableFormator::new(40, 99);
+        let text = "[%header,cols=\"2*\"]\n|===\n| a | b\n2+| both\n|===";
+        assert_eq!(
+            formator.format(text, &TableMode::Asciidoc).unwrap(),
+            "[%header,cols=\"2*\"]\n\
+             |========\n\
+             | a   | b\n\
+             \n\
+             2+| both\n\
+             |========\n"
+        );
+        // 列有变化时在原来的位置重新生成 cols
+        let text = "[%header,cols=\"<,>\",frame=none]\n|===\n| a | b\n|===";
+        let edited = formator.edit(text, &TableOp::InsertColumn(1)).unwrap();
+        assert_eq!(
+            edited.lines().next().unwrap(),
+            "[%header,cols=\"<,<,>\",frame=none]"
+        );
+    }
+
+    #[test]
+    fn test_decimal_align() {
+        let mut data = TableData::new(1, 4, 40, 99);
+        for (y, v) in ["price", "1.5", "12.25", "300"].iter().enumerate() {
+            data.set_cell(0, y, v);
+        }
+        data.set_align(0, Align::Decimal);
+        assert_eq!(
+            data.to_gfm_markdown_table(),
+            " price\n-----:\n  1.5\n 12.25\n300\n"
+        );
+        // 多行单元格的每一行分别按小数点对齐
+        let mut data = TableData::new(1, 2, 40, 99);
+        data.set_cell(0, 0, "value");
+        data.set_cell(0, 1, "123\n4.5");
+        data.set_align(0, Align::Decimal);
+        assert_eq!(
+            data.to_rst_grid_table(),
+            "+-------+\n\
+             | value |\n\
+             +-------+\n\
+             | 123   |\n\
+             |   4.5 |\n\
+             +-------+\n"
+        );
+    }
 }
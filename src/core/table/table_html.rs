@@ -0,0 +1,380 @@
+use super::TableData;
+use super::table_cell::CellStyle;
+use super::table_data::Align;
+
+/// 是否包含 html 表格
+pub fn is_html_table(text: &str) -> bool {
+    text.to_ascii_lowercase().contains("<table")
+}
+
+/// 转义 html 中的特殊字符，换行转为 `<br>`
+pub fn escape_html(text: &str) -> String {
+    let mut result = String::new();
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\n' => result.push_str("<br>"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+// 解码常用的 html 实体，未知的实体保持原样
+fn decode_entities(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|end| *end < 10) else {
+            result.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                None => entity
+                    .strip_prefix('#')
+                    .and_then(|n| n.parse().ok())
+                    .and_then(char::from_u32),
+            },
+        };
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+// 读取标签属性，例如 `colspan="2"`、`align=right`
+pub fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let lower = attributes.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(i) = lower[from..].find(name) {
+        let start = from + i;
+        from = start + name.len();
+        let before = lower[..start].chars().last();
+        if before.is_some_and(|c| !c.is_whitespace()) {
+            continue;
+        }
+        let Some(value) = attributes[from..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(q @ ('"' | '\'')) => value[1..].split(q).next().unwrap_or_default(),
+            _ => value.split(char::is_whitespace).next().unwrap_or_default(),
+        };
+        return Some(value.to_string());
+    }
+    None
+}
+
+// 单元格的对齐方式，支持 align 属性和 style 中的 text-align
+pub fn cell_align(attributes: &str) -> Option<Align> {
+    let value = attribute(attributes, "align").or_else(|| {
+        let style = attribute(attributes, "style")?.to_ascii_lowercase();
+        let (_, rest) = style.split_once("text-align")?;
+        let value = rest.trim_start().strip_prefix(':')?;
+        Some(value.split(';').next()?.trim().to_string())
+    })?;
+    match value.to_ascii_lowercase().as_str() {
+        "left" => Some(Align::Left),
+        "center" => Some(Align::Center),
+        "right" => Some(Align::Right),
+        _ => None,
+    }
+}
+
+/// html 中的单元格
+#[derive(Debug, Default)]
+pub struct HtmlCell {
+    pub text: String,
+    pub colspan: usize,
+    pub rowspan: usize,
+    pub halign: Option<Align>,
+    pub th: bool,
+}
+
+impl HtmlCell {
+    // 添加文本，连续的空白合并为一个空格
+    fn push_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !self.text.is_empty() && !self.text.ends_with([' ', '\n']) {
+                    self.text.push(' ');
+                }
+            } else {
+                self.text.push(c);
+            }
+        }
+    }
+
+    fn push_line_break(&mut self) {
+        let len = self.text.trim_end_matches(' ').len();
+        self.text.truncate(len);
+        self.text.push('\n');
+    }
+
+    // 去掉每行首尾的空白和首尾的空行
+    fn finish(&self) -> String {
+        let lines: Vec<&str> = self.text.lines().map(|l| l.trim()).collect();
+        lines.join("\n").trim_matches('\n').to_string()
+    }
+}
+
+/// 解析第一个 `<table>`，支持 `<thead>`、`<caption>`、`colspan`、`rowspan` 和 `<br>`
+pub fn parse_html(text: &str, cell_max_w: usize, line_max_w: usize) -> Option<TableData> {
+    let start = text.to_ascii_lowercase().find("<table")?;
+    let mut rest = &text[start..];
+
+    let mut rows: Vec<Vec<HtmlCell>> = Vec::new();
+    let mut cell: Option<HtmlCell> = None;
+    let mut caption: Option<HtmlCell> = None;
+    let mut in_thead = false;
+    let mut head_rows = 0;
+    // 嵌套表格的内容作为单元格文本
+    let mut depth = 0;
+    let mut title = String::new();
+
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            break;
+        };
+        let content = decode_entities(&rest[..open]);
+        if let Some(target) = cell.as_mut().or(caption.as_mut()) {
+            target.push_text(&content);
+        }
+        rest = &rest[open..];
+        // 注释
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map(|end| &rest[end + 3..]).unwrap_or("");
+            continue;
+        }
+        let Some(close) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+
+        let closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/');
+        let name_len = tag
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(tag.len());
+        let name = tag[..name_len].to_ascii_lowercase();
+        let attributes = &tag[name_len..];
+
+        if name == "table" {
+            depth += if closing { -1 } else { 1 };
+            if depth == 0 {
+                break;
+            }
+            continue;
+        }
+        if depth > 1 {
+            continue;
+        }
+        match (name.as_str(), closing) {
+            ("caption", false) => caption = Some(HtmlCell::default()),
+            ("thead", _) => in_thead = !closing,
+            ("tr", false) => {
+                // 上一行的单元格可以省略结束标签
+                if let Some(c) = cell.take()
+                    && let Some(row) = rows.last_mut()
+                {
+                    row.push(c);
+                }
+                rows.push(Vec::new());
+                if in_thead {
+                    head_rows += 1;
+                }
+            }
+            ("td" | "th", false) => {
+                if let Some(c) = cell.take()
+                    && let Some(row) = rows.last_mut()
+                {
+                    row.push(c);
+                }
+                let span = |name| {
+                    attribute(attributes, name)
+                        .and_then(|v| v.trim().parse::<usize>().ok())
+                        .filter(|v| *v > 0)
+                        .unwrap_or(1)
+                };
+                cell = Some(HtmlCell {
+                    text: String::new(),
+                    colspan: span("colspan"),
+                    rowspan: span("rowspan"),
+                    halign: cell_align(attributes),
+                    th: name == "th",
+                });
+                if rows.is_empty() {
+                    rows.push(Vec::new());
+                }
+            }
+            ("td" | "th", true) | ("tr", true) => {
+                if let Some(c) = cell.take()
+                    && let Some(row) = rows.last_mut()
+                {
+                    row.push(c);
+                }
+            }
+            ("br", _) => {
+                if let Some(target) = cell.as_mut().or(caption.as_mut()) {
+                    target.push_line_break();
+                }
+            }
+            ("p" | "div" | "li", true) => {
+                if let Some(target) = cell.as_mut() {
+                    target.push_line_break();
+                }
+            }
+            ("caption", true) => {
+                if let Some(c) = caption.take() {
+                    title = c.finish();
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(c) = cell.take()
+        && let Some(row) = rows.last_mut()
+    {
+        row.push(c);
+    }
+    build_table(rows, head_rows, &title, cell_max_w, line_max_w)
+}
+
+/// 按行放入单元格生成表格，mediawiki 表格也使用
+pub fn build_table(
+    mut rows: Vec<Vec<HtmlCell>>,
+    head_rows: usize,
+    title: &str,
+    cell_max_w: usize,
+    line_max_w: usize,
+) -> Option<TableData> {
+    rows.retain(|row| !row.is_empty());
+    if rows.is_empty() {
+        return None;
+    }
+
+    // 依次放入表格，跳过被上方跨行单元格占用的位置
+    let mut occupied: Vec<Vec<bool>> = vec![Vec::new(); rows.len()];
+    let mut placed = Vec::new();
+    for (y, row) in rows.into_iter().enumerate() {
+        let mut x = 0;
+        for cell in row {
+            while occupied[y].get(x).copied().unwrap_or(false) {
+                x += 1;
+            }
+            let rowspan = cell.rowspan.min(occupied.len() - y);
+            for line in occupied.iter_mut().skip(y).take(rowspan) {
+                if line.len() < x + cell.colspan {
+                    line.resize(x + cell.colspan, false);
+                }
+                line[x..x + cell.colspan].fill(true);
+            }
+            x += cell.colspan;
+            placed.push((x - cell.colspan, y, rowspan, cell));
+        }
+    }
+    let w = occupied.iter().map(|line| line.len()).max()?;
+
+    let mut data = TableData::new(w, occupied.len(), cell_max_w, line_max_w);
+    for (x, y, rowspan, cell) in placed.iter() {
+        if let Some(target) = data.cell_at_mut(*x, *y) {
+            target.text = cell.finish();
+            target.halign = cell.halign;
+            // 不在表头中的 th 使用 asciidoc 的表头样式
+            if cell.th && *y >= head_rows.max(1) {
+                target.style = CellStyle::Header;
+            }
+        }
+        data.merge(*x, *y, cell.colspan, *rowspan);
+    }
+    let first_row_th = placed.iter().filter(|p| p.1 == 0).all(|p| p.3.th);
+    data.header = head_rows > 0 || first_row_th;
+    if !title.is_empty() {
+        // 标题使用 asciidoc 的写法
+        data.title = format!(".{}", title);
+    }
+    data.trim();
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_html() {
+        let text = "<meta charset='utf-8'><TABLE class=\"x\">\n\
+            <caption>Prices &amp; stock</caption>\n\
+            <thead><tr><th>Name</th><th colspan=\"2\">Value</th></tr></thead>\n\
+            <tbody>\n\
+            <tr><td rowspan=2>A&nbsp;B</td><td align=\"right\">1</td>\n\
+            <td style=\"color: red; text-align: center\">x<br/>y</td></tr>\n\
+            <tr><td><p>one</p><p>two</p></td><td>&#60;3&#x3e;</td></tr>\n\
+            </tbody></table><p>after</p>";
+        let data = parse_html(text, 40, 99).unwrap();
+        assert_eq!((data.w, data.h), (3, 3));
+        assert!(data.header);
+        assert_eq!(data.title, ".Prices & stock");
+        assert_eq!(data.cell_at(1, 0).unwrap().colspan, 2);
+        assert_eq!(data.cell_at(0, 1).unwrap().rowspan, 2);
+        assert!(data.cell_at(0, 2).unwrap().covered);
+        assert_eq!(data.cell(0, 1), Some("A B"));
+        assert_eq!(data.cell_at(1, 1).unwrap().halign, Some(Align::Right));
+        assert_eq!(data.cell_at(2, 1).unwrap().halign, Some(Align::Center));
+        assert_eq!(data.cell(2, 1), Some("x\ny"));
+        assert_eq!(data.cell(1, 2), Some("one\ntwo"));
+        assert_eq!(data.cell(2, 2), Some("<3>"));
+    }
+
+    #[test]
+    fn test_html_round_trip() {
+        let html = "\
+<table>
+  <thead>
+    <tr>
+      <th>a</th>
+      <th>b &amp; c</th>
+    </tr>
+  </thead>
+  <tbody>
+    <tr>
+      <td colspan=\"2\" style=\"text-align: right\">x<br>y</td>
+    </tr>
+    <tr>
+      <th>1</th>
+      <td>2</td>
+    </tr>
+  </tbody>
+</table>
+";
+        let data = parse_html(html, 40, 99).unwrap();
+        assert_eq!(data.cell_at(0, 2).unwrap().style, CellStyle::Header);
+        assert_eq!(data.to_html_table(), html);
+    }
+}
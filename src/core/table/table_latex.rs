@@ -0,0 +1,44 @@
+use super::table_data::Align;
+
+/// 转义 LaTeX 中的特殊字符
+pub fn escape_latex(text: &str) -> String {
+    let mut result = String::new();
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(c);
+            }
+            '~' => result.push_str("\\textasciitilde{}"),
+            '^' => result.push_str("\\textasciicircum{}"),
+            '\\' => result.push_str("\\textbackslash{}"),
+            // tabular 的单元格不能换行
+            '\n' => result.push(' '),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// tabular 的列格式，没有小数点对齐，使用右对齐
+pub fn column_spec(align: Align) -> char {
+    match align {
+        Align::Left => 'l',
+        Align::Center => 'c',
+        Align::Right | Align::Decimal => 'r',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_latex() {
+        assert_eq!(
+            escape_latex("50% of $x_1 & #{y} ~ ^ \\"),
+            "50\\% of \\$x\\_1 \\& \\#\\{y\\} \\textasciitilde{} \\textasciicircum{} \\textbackslash{}"
+        );
+        assert_eq!(column_spec(Align::Decimal), 'r');
+    }
+}
@@ -0,0 +1,161 @@
+use super::TableData;
+use super::table_data::Align;
+
+/// 是否是 org 表格：包含 `|---+---|` 分隔线、`#+TBLFM:` 或者对齐标记行
+pub fn is_org_table(text: &str) -> bool {
+    text.lines().map(str::trim).any(|line| {
+        (line.starts_with("|-") && line.contains("-+-"))
+            || line.starts_with("#+TBLFM:")
+            || parse_cookies(&split_row(line)).is_some()
+    })
+}
+
+/// org 分隔线 `|---+---|`
+pub fn is_hline(line: &str) -> bool {
+    line.trim().starts_with("|-")
+}
+
+// 拆分 `| a | b |` 中的单元格
+fn split_row(line: &str) -> Vec<&str> {
+    let line = line.trim();
+    let Some(line) = line.strip_prefix('|') else {
+        return Vec::new();
+    };
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(str::trim).collect()
+}
+
+/// 对齐标记中的对齐方式和宽度
+pub type Cookie = (Option<Align>, Option<usize>);
+
+/// 解析对齐标记 `<l>`、`<r10>`、`<8>`
+pub fn parse_cookie(cell: &str) -> Option<Cookie> {
+    let inner = cell.strip_prefix('<')?.strip_suffix('>')?;
+    let (align, width) = match inner.chars().next()? {
+        'l' => (Some(Align::Left), &inner[1..]),
+        'c' => (Some(Align::Center), &inner[1..]),
+        'r' => (Some(Align::Right), &inner[1..]),
+        _ => (None, inner),
+    };
+    if width.is_empty() {
+        return align.map(|a| (Some(a), None));
+    }
+    Some((align, Some(width.parse().ok()?)))
+}
+
+// 整行都是对齐标记或者空单元格时才是标记行
+fn parse_cookies(cells: &[&str]) -> Option<Vec<Option<Cookie>>> {
+    if cells.iter().all(|c| c.is_empty()) {
+        return None;
+    }
+    cells
+        .iter()
+        .map(|c| {
+            if c.is_empty() {
+                Some(None)
+            } else {
+                parse_cookie(c).map(Some)
+            }
+        })
+        .collect()
+}
+
+/// 生成对齐标记，默认左对齐并且没有宽度时为空
+pub fn cookie(align: Align, width: Option<usize>) -> String {
+    let align = match align {
+        Align::Left if width.is_none() => return String::new(),
+        Align::Left => "l",
+        Align::Center => "c",
+        Align::Right | Align::Decimal => "r",
+    };
+    let width = width.map(|w| w.to_string()).unwrap_or_default();
+    format!("<{}{}>", align, width)
+}
+
+/// 解析 org 表格，支持分隔线、对齐标记行、`#+CAPTION:` 和 `#+TBLFM:`
+pub fn parse_org(text: &str, cell_max_w: usize, line_max_w: usize) -> Option<TableData> {
+    let mut rows: Vec<Vec<&str>> = Vec::new();
+    let mut rules: Vec<usize> = Vec::new();
+    let mut formulas: Vec<String> = Vec::new();
+    let mut cookies = Vec::new();
+    let mut title = String::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(caption) = line.strip_prefix("#+CAPTION:") {
+            title = format!(".{}", caption.trim());
+        } else if line.starts_with("#+TBLFM:") {
+            formulas.push(line.to_string());
+        } else if is_hline(line) {
+            rules.push(rows.len());
+        } else if line.starts_with('|') {
+            let cells = split_row(line);
+            match parse_cookies(&cells) {
+                Some(c) => cookies = c,
+                None => rows.push(cells),
+            }
+        }
+    }
+    let w = rows.iter().map(|r| r.len()).max()?;
+
+    let mut data = TableData::new(w, rows.len(), cell_max_w, line_max_w);
+    for (y, row) in rows.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            data.set_cell(x, y, cell);
+        }
+    }
+    for (x, cookie) in cookies.into_iter().enumerate() {
+        if let (Some((align, width)), Some(column)) = (cookie, data.column_mut(x)) {
+            column.align = align.unwrap_or_default();
+            column.max_width = width;
+        }
+    }
+    // 第一条数据行下方的分隔线表示表头
+    data.header = rows.len() > 1 && rules.contains(&1);
+    rules.dedup();
+    data.rules = rules;
+    data.formulas = formulas;
+    data.title = title;
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookie() {
+        assert_eq!(parse_cookie("<r>"), Some((Some(Align::Right), None)));
+        assert_eq!(parse_cookie("<c10>"), Some((Some(Align::Center), Some(10))));
+        assert_eq!(parse_cookie("<8>"), Some((None, Some(8))));
+        assert_eq!(parse_cookie("<x>"), None);
+        assert_eq!(parse_cookie("<>"), None);
+        assert_eq!(cookie(Align::Right, Some(5)), "<r5>");
+        assert_eq!(cookie(Align::Left, None), "");
+        assert!(is_org_table("| a | b |\n|---+---|\n| 1 | 2 |"));
+        assert!(!is_org_table("| a | b |\n|---|---|\n| 1 | 2 |"));
+    }
+
+    #[test]
+    fn test_parse_org() {
+        let text = "\
+#+CAPTION: Totals
+|------+-----|
+| Name | Qty |
+|------+-----|
+|      | <r> |
+| a    |   1 |
+| b    |   2 |
+|------+-----|
+| sum  |   3 |
+|------+-----|
+#+TBLFM: @>$2=vsum(@I..@II)
+";
+        let data = parse_org(text, 40, 99).unwrap();
+        assert_eq!((data.w, data.h), (2, 4));
+        assert!(data.header);
+        assert_eq!(data.title, ".Totals");
+        assert_eq!(data.align(1), Align::Right);
+        assert_eq!(data.rules, vec![0, 1, 3, 4]);
+        assert_eq!(data.formulas, vec!["#+TBLFM: @>$2=vsum(@I..@II)"]);
+        assert_eq!(data.to_org_table(), text);
+    }
+}
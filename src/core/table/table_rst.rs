@@ -0,0 +1,262 @@
+use super::TableData;
+use crate::utils;
+
+/// 网格表格以 `+---` 或者 `+===` 开头
+pub fn is_rst_grid(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with("+-") || line.starts_with("+=")
+}
+
+/// 简单表格以 `=====  =====` 开头
+pub fn is_rst_simple(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with("==") && line.chars().all(|c| c == '=' || c == ' ')
+}
+
+// 按显示宽度展开，宽字符后面补 '\0'，方便按列定位
+fn display_cells(line: &str) -> Vec<char> {
+    let mut cells = Vec::new();
+    for c in line.chars() {
+        cells.push(c);
+        let width = utils::cn_length(c.encode_utf8(&mut [0; 4]));
+        cells.extend(std::iter::repeat_n('\0', width.saturating_sub(1)));
+    }
+    cells
+}
+
+// 取出 [start, end) 范围内的文本
+fn collect_text(cells: &[char], start: usize, end: usize) -> String {
+    let end = end.min(cells.len());
+    if start >= end {
+        return String::new();
+    }
+    let text: String = cells[start..end].iter().filter(|c| **c != '\0').collect();
+    text.trim().to_string()
+}
+
+// 连续的 symbol 所在的范围，例如 `===  ===` 中的两段
+fn runs(cells: &[char], symbol: char) -> Vec<(usize, usize)> {
+    let mut result = Vec::new();
+    let mut start = None;
+    for (i, c) in cells.iter().chain([' '].iter()).enumerate() {
+        match (start, *c == symbol) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                result.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+// 去掉共同的缩进
+fn unindent(text: &str) -> Vec<Vec<char>> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    let indent = lines
+        .iter()
+        .map(|l| l.len() - l.trim_start_matches(' ').len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| display_cells(l[indent..].trim_end()))
+        .collect()
+}
+
+/// 解析网格表格，支持合并单元格、多行单元格和 `+===+` 表头分隔
+pub fn parse_rst_grid(text: &str, cell_max_w: usize, line_max_w: usize) -> Option<TableData> {
+    let lines = unindent(text);
+    let at = |r: usize, c: usize| lines.get(r).and_then(|l| l.get(c)).copied().unwrap_or(' ');
+    // 行边界：带有 +- 或者 += 的行，第一列跨行时不以 + 开头
+    let is_boundary = |l: &Vec<char>| {
+        l.windows(2)
+            .any(|w| w[0] == '+' && (w[1] == '-' || w[1] == '='))
+    };
+    let rows: Vec<usize> = (0..lines.len())
+        .filter(|r| is_boundary(&lines[*r]))
+        .collect();
+    // 列边界：所有边界行中 + 的位置
+    let mut cols: Vec<usize> = rows
+        .iter()
+        .flat_map(|r| {
+            lines[*r]
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| **c == '+')
+                .map(|(i, _)| i)
+        })
+        .collect();
+    cols.sort();
+    cols.dedup();
+    if rows.len() < 2 || cols.len() < 2 {
+        return None;
+    }
+
+    let (w, h) = (cols.len() - 1, rows.len() - 1);
+    let mut data = TableData::new(w, h, cell_max_w, line_max_w);
+    let mut assigned = vec![vec![false; w]; h];
+    for ri in 0..h {
+        for ci in 0..w {
+            if assigned[ri][ci] {
+                continue;
+            }
+            let (top, left) = (rows[ri], cols[ci]);
+            // 右边界是第一行内容中下一个 |
+            let mut cj = ci + 1;
+            while cj < w && at(top + 1, cols[cj]) != '|' {
+                cj += 1;
+            }
+            // 下边界是下一个带有 - 或者 = 的边界行
+            let mut rj = ri + 1;
+            while rj < h && !matches!(at(rows[rj], left + 1), '-' | '=') {
+                rj += 1;
+            }
+            for line in assigned.iter_mut().take(rj).skip(ri) {
+                line[ci..cj].fill(true);
+            }
+            let content: Vec<String> = (top + 1..rows[rj])
+                .map(|r| collect_text(&lines[r], left + 1, cols[cj]))
+                .collect();
+            if let Some(cell) = data.cell_at_mut(ci, ri) {
+                cell.text = content.join("\n").trim_matches('\n').to_string();
+            }
+            data.merge(ci, ri, cj - ci, rj - ri);
+        }
+    }
+    data.header = rows.iter().any(|r| lines[*r].contains(&'='));
+    data.trim();
+    Some(data)
+}
+
+/// 解析简单表格，支持 `---` 标记的跨列和第一列为空的续行
+pub fn parse_rst_simple(text: &str, cell_max_w: usize, line_max_w: usize) -> Option<TableData> {
+    let lines = unindent(text);
+    let only = |l: &[char], symbol: char| {
+        l.contains(&symbol) && l.iter().all(|c| *c == symbol || *c == ' ')
+    };
+    let first = lines.iter().position(|l| only(l, '='))?;
+    let columns = runs(&lines[first], '=');
+    let w = columns.len();
+
+    // 每一行的原文，续行属于同一行
+    let mut rows: Vec<Vec<&[char]>> = Vec::new();
+    let mut merges: Vec<(usize, usize, usize)> = Vec::new();
+    let mut borders = 0;
+    let mut header_rows = 0;
+    let mut after_border = true;
+    // x 列的范围，最后一列的内容可以超出边框
+    let column_end =
+        |x: usize, line: &[char]| columns.get(x + 1).map(|c| c.0).unwrap_or(line.len());
+    for line in lines.iter().skip(first) {
+        if only(line, '=') {
+            borders += 1;
+            if borders == 2 {
+                header_rows = rows.len();
+            }
+            after_border = true;
+            continue;
+        }
+        // 跨列标记，每一段 - 覆盖的列合并为一个单元格
+        if only(line, '-') && !rows.is_empty() {
+            for (start, end) in runs(line, '-') {
+                let covered: Vec<usize> = (0..w)
+                    .filter(|i| columns[*i].0 >= start && columns[*i].0 < end)
+                    .collect();
+                if let (Some(x), true) = (covered.first(), covered.len() > 1) {
+                    merges.push((*x, rows.len() - 1, covered.len()));
+                }
+            }
+            continue;
+        }
+        // 第一列为空的是上一行的续行
+        let continued = collect_text(line, columns[0].0, column_end(0, line)).is_empty();
+        match rows.last_mut() {
+            Some(last) if continued && !after_border => last.push(line),
+            _ => rows.push(vec![line]),
+        }
+        after_border = false;
+    }
+    if rows.is_empty() {
+        return None;
+    }
+
+    let mut data = TableData::new(w, rows.len(), cell_max_w, line_max_w);
+    for (y, row) in rows.iter().enumerate() {
+        for (x, (start, _)) in columns.iter().enumerate() {
+            // 跨列的单元格取整个范围内的文本
+            let last = merges
+                .iter()
+                .find(|m| m.0 == x && m.1 == y)
+                .map(|m| x + m.2 - 1)
+                .unwrap_or(x);
+            let text: Vec<String> = row
+                .iter()
+                .map(|line| collect_text(line, *start, column_end(last, line)))
+                .filter(|t| !t.is_empty())
+                .collect();
+            if let Some(cell) = data.cell_at_mut(x, y) {
+                cell.text = text.join("\n");
+            }
+        }
+    }
+    for (x, y, colspan) in merges {
+        data.merge(x, y, colspan, 1);
+    }
+    // 三条边框时，第一条和第二条之间是表头
+    data.header = borders >= 3 && header_rows > 0;
+    data.trim();
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid() {
+        let text = "\
++------------+---------+
+| Header 1   | Header 2|
++============+=========+
+| wide cell            |
++------------+---------+
+| tall       | a       |
+| two lines  |         |
++            +---------+
+|            | 中文    |
++------------+---------+";
+        let data = parse_rst_grid(text, 40, 99).unwrap();
+        assert_eq!((data.w, data.h), (2, 4));
+        assert!(data.header);
+        assert_eq!(data.cell(1, 0), Some("Header 2"));
+        assert_eq!(data.cell_at(0, 1).unwrap().colspan, 2);
+        assert_eq!(data.cell(0, 1), Some("wide cell"));
+        assert_eq!(data.cell_at(0, 2).unwrap().rowspan, 2);
+        assert_eq!(data.cell(0, 2), Some("tall\ntwo lines"));
+        assert_eq!(data.cell(1, 3), Some("中文"));
+    }
+
+    #[test]
+    fn test_parse_simple() {
+        let text = "
+  =====  =====  ======
+     Inputs     Output
+  ------------  ------
+  A      B      A or B
+  =====  =====  ======
+  False  False  False
+         True   long
+                text
+  =====  =====  ======";
+        let data = parse_rst_simple(text, 40, 99).unwrap();
+        assert_eq!((data.w, data.h), (3, 3));
+        assert!(data.header);
+        assert_eq!(data.cell_at(0, 0).unwrap().colspan, 2);
+        assert_eq!(data.cell(0, 0), Some("Inputs"));
+        assert_eq!(data.cell(2, 1), Some("A or B"));
+        assert_eq!(data.cell(1, 2), Some("False\nTrue"));
+        assert_eq!(data.cell(2, 2), Some("False\nlong\ntext"));
+    }
+}
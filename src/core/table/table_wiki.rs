@@ -0,0 +1,167 @@
+use super::TableData;
+use super::table_html::{HtmlCell, attribute, build_table, cell_align};
+
+/// mediawiki 表格以 `{|` 开头
+pub fn is_mediawiki(line: &str) -> bool {
+    line.trim().starts_with("{|")
+}
+
+/// jira 表格以 `||` 表头开头
+pub fn is_jira(line: &str) -> bool {
+    line.trim().starts_with("||")
+}
+
+// 按 sep 拆分，`[[链接|文本]]`、`{{模板|参数}}` 和 `[文本|链接]` 中的不拆分
+fn split_outside<'a>(text: &'a str, sep: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut i = 0;
+    let bytes = text.as_bytes();
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && text[i..].starts_with(sep) => {
+                parts.push(&text[start..i]);
+                i += sep.len();
+                start = i;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+// 换行写作 `<br>`
+fn line_breaks(text: &str) -> String {
+    text.replace("<br />", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br>", "\n")
+}
+
+// mediawiki 单元格 `colspan="2" | 内容`，第一个单独的 | 前面是属性
+fn wiki_cell(raw: &str, th: bool) -> HtmlCell {
+    let parts = split_outside(raw, "|");
+    let (attributes, content) = match parts.as_slice() {
+        [attributes, ..] if parts.len() > 1 && attributes.contains('=') => {
+            (*attributes, &raw[attributes.len() + 1..])
+        }
+        _ => ("", raw),
+    };
+    let span = |name| {
+        attribute(attributes, name)
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(1)
+    };
+    HtmlCell {
+        text: line_breaks(content.trim()),
+        colspan: span("colspan"),
+        rowspan: span("rowspan"),
+        halign: cell_align(attributes),
+        th,
+    }
+}
+
+/// 解析 mediawiki 表格，支持 `|+` 标题、`!` 表头、`||` 同行单元格、合并单元格和多行内容
+pub fn parse_mediawiki(text: &str, cell_max_w: usize, line_max_w: usize) -> Option<TableData> {
+    let mut rows: Vec<Vec<HtmlCell>> = vec![Vec::new()];
+    let mut title = String::new();
+    for line in text.lines().map(str::trim) {
+        if line.starts_with("{|") || line.is_empty() {
+            continue;
+        }
+        if line.starts_with("|}") {
+            break;
+        }
+        if let Some(caption) = line.strip_prefix("|+") {
+            title = caption.trim().to_string();
+        } else if line.starts_with("|-") {
+            rows.push(Vec::new());
+        } else if let Some(cells) = line.strip_prefix('!') {
+            // 表头单元格可以用 !! 或者 || 分隔
+            let row = rows.last_mut()?;
+            for cell in split_outside(cells, "!!")
+                .into_iter()
+                .flat_map(|c| split_outside(c, "||"))
+            {
+                row.push(wiki_cell(cell, true));
+            }
+        } else if let Some(cells) = line.strip_prefix('|') {
+            let row = rows.last_mut()?;
+            for cell in split_outside(cells, "||") {
+                row.push(wiki_cell(cell, false));
+            }
+        } else if let Some(cell) = rows.last_mut().and_then(|r| r.last_mut()) {
+            // 不以 | 开头的行是上一个单元格的后续内容
+            cell.text.push('\n');
+            cell.text.push_str(&line_breaks(line));
+        }
+    }
+    build_table(rows, 0, &title, cell_max_w, line_max_w)
+}
+
+/// 解析 jira/confluence 表格，`||` 分隔的是表头单元格，`\\` 为换行
+pub fn parse_jira(text: &str, cell_max_w: usize, line_max_w: usize) -> Option<TableData> {
+    let mut rows: Vec<Vec<HtmlCell>> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|l| l.starts_with('|')) {
+        let th = line.starts_with("||");
+        let line = line.trim_start_matches('|').trim_end_matches('|');
+        let sep = if th { "||" } else { "|" };
+        let row = split_outside(line, sep)
+            .into_iter()
+            .map(|cell| HtmlCell {
+                text: cell.trim().replace("\\\\", "\n"),
+                colspan: 1,
+                rowspan: 1,
+                halign: None,
+                th,
+            })
+            .collect();
+        rows.push(row);
+    }
+    build_table(rows, 0, "", cell_max_w, line_max_w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::table::Align;
+
+    #[test]
+    fn test_parse_mediawiki() {
+        let text = "\
+{| class=\"wikitable\"
+|+ Fruit
+|-
+! Name !! Price
+|-
+| [[Apple|apples]] || style=\"text-align: right\" | 1
+|-
+| colspan=\"2\" | a long
+note<br>here
+|}";
+        let data = parse_mediawiki(text, 40, 99).unwrap();
+        assert_eq!((data.w, data.h), (2, 3));
+        assert!(data.header);
+        assert_eq!(data.title, ".Fruit");
+        assert_eq!(data.cell(0, 1), Some("[[Apple|apples]]"));
+        assert_eq!(data.cell_at(1, 1).unwrap().halign, Some(Align::Right));
+        assert_eq!(data.cell_at(0, 2).unwrap().colspan, 2);
+        assert_eq!(data.cell(0, 2), Some("a long\nnote\nhere"));
+    }
+
+    #[test]
+    fn test_parse_jira() {
+        let text = "||Key||Summary||\n|ABC-1|[link|http://x]|\n|ABC-2|two\\\\lines|";
+        let data = parse_jira(text, 40, 99).unwrap();
+        assert_eq!((data.w, data.h), (2, 3));
+        assert!(data.header);
+        assert_eq!(data.cell(1, 1), Some("[link|http://x]"));
+        assert_eq!(data.cell(1, 2), Some("two\nlines"));
+    }
+}
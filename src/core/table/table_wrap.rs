@@ -0,0 +1,177 @@
+use crate::utils;
+
+fn char_width(c: char) -> usize {
+    utils::cn_length(c.encode_utf8(&mut [0; 4]))
+}
+
+// 不能出现在行首的标点，跟在前一个字后面
+fn is_closing_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        '，' | '。'
+            | '、'
+            | '；'
+            | '：'
+            | '！'
+            | '？'
+            | '）'
+            | '】'
+            | '」'
+            | '』'
+            | '》'
+            | '〉'
+            | '…'
+            | ','
+            | '.'
+            | ';'
+            | ':'
+            | '!'
+            | '?'
+            | ')'
+            | ']'
+            | '}'
+    )
+}
+
+// 拆分为可以断行的片段：英文按单词，宽字符每个字一段，返回片段前面是否有空格
+fn tokens(line: &str) -> Vec<(bool, String)> {
+    let mut result: Vec<(bool, String)> = Vec::new();
+    let mut space = false;
+    let mut word = false;
+    for c in line.chars() {
+        if c.is_whitespace() {
+            space = true;
+            word = false;
+            continue;
+        }
+        match result.last_mut() {
+            Some((_, token))
+                if !space && (is_closing_punctuation(c) || (word && char_width(c) == 1)) =>
+            {
+                token.push(c);
+            }
+            _ => result.push((space, c.to_string())),
+        }
+        word = char_width(c) == 1;
+        space = false;
+    }
+    result
+}
+
+// 过长的片段按宽度强制断开
+fn split_token(token: &str, width: usize) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut w = 0;
+    for c in token.chars() {
+        let cw = char_width(c);
+        if w + cw > width && w > 0 {
+            parts.push(String::new());
+            w = 0;
+        }
+        if let Some(part) = parts.last_mut() {
+            part.push(c);
+        }
+        w += cw;
+    }
+    parts
+}
+
+fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    if utils::cn_length(line) <= width {
+        return vec![line.to_string()];
+    }
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut current_w = 0;
+    for (space, token) in tokens(line) {
+        let token_w = utils::cn_length(&token);
+        let gap = usize::from(space && !current.is_empty());
+        if current_w + gap + token_w <= width {
+            if gap > 0 {
+                current.push(' ');
+            }
+            current.push_str(&token);
+            current_w += gap + token_w;
+            continue;
+        }
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        let mut parts = split_token(&token, width);
+        current = parts.pop().unwrap_or_default();
+        current_w = utils::cn_length(&current);
+        lines.extend(parts);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// 按显示宽度折行，英文在空格处断开，中文可以在任意两个字之间断开，原有的换行保留
+pub fn wrap_text(text: &str, width: usize) -> String {
+    let width = width.max(1);
+    let lines: Vec<String> = text.lines().flat_map(|l| wrap_line(l, width)).collect();
+    lines.join("\n")
+}
+
+/// 总宽度超过 available 时，按原宽度的比例缩小每一列，每列不小于 min
+pub fn fit_widths(widths: &[usize], available: usize, min: usize) -> Vec<usize> {
+    let total: usize = widths.iter().sum();
+    if total <= available {
+        return widths.to_vec();
+    }
+    // 比 min 还窄的列保持原样，剩下的宽度按比例分给其余列
+    let fixed: usize = widths.iter().filter(|w| **w <= min).sum();
+    let flexible = total - fixed;
+    let room = available.saturating_sub(fixed);
+    let mut result: Vec<usize> = widths
+        .iter()
+        .map(|w| {
+            if *w <= min {
+                *w
+            } else {
+                (w * room / flexible).max(min)
+            }
+        })
+        .collect();
+    // 取整剩下的宽度从最宽的列开始补
+    let mut rest = available.saturating_sub(result.iter().sum());
+    let mut order: Vec<usize> = (0..widths.len()).filter(|x| widths[*x] > min).collect();
+    order.sort_by_key(|x| std::cmp::Reverse(widths[*x]));
+    for x in order.iter().cycle().take(order.len() * 2) {
+        if rest == 0 {
+            break;
+        }
+        if result[*x] < widths[*x] {
+            result[*x] += 1;
+            rest -= 1;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(wrap_text("the quick brown fox", 10), "the quick\nbrown fox");
+        assert_eq!(wrap_text("abcdefghij", 4), "abcd\nefgh\nij");
+        assert_eq!(
+            wrap_text("中文可以在任意位置断开", 8),
+            "中文可以\n在任意位\n置断开"
+        );
+        assert_eq!(wrap_text("你好，世界。", 6), "你好，\n世界。");
+        assert_eq!(wrap_text("mixed 中文 text", 8), "mixed 中\n文 text");
+        assert_eq!(wrap_text("a\nb", 4), "a\nb");
+    }
+
+    #[test]
+    fn test_fit_widths() {
+        assert_eq!(fit_widths(&[10, 20], 40, 3), vec![10, 20]);
+        assert_eq!(fit_widths(&[10, 30], 20, 3), vec![5, 15]);
+        assert_eq!(fit_widths(&[3, 40, 20], 30, 3), vec![3, 18, 9]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::AsciiBoxMap;
+use crate::core::{AsciiBoxMap, unicode_to_svgbob};
 use crate::utils;
 use adw::prelude::*;
 use adw::subclass::prelude::*;
@@ -199,6 +199,8 @@ impl AsciiboxPage {
     fn execute_preview_svgbob(&self) {
         let buffer = self.imp().out_view.get().buffer();
         let content = buffer.text(&buffer.bounds().0, &buffer.bounds().1, false);
+        // svgbob 不识别 unicode 制表符，先转换为 ascii
+        let content = unicode_to_svgbob(content.as_str());
         let svg_content = svgbob::to_svg_string_pretty(content.as_str());
         self.imp().svg_content.set(svg_content.clone());
 
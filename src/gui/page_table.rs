@@ -1,8 +1,9 @@
-use crate::core::{TableFormator, TableMode};
+use crate::core::{BoxBorder, SortMode, TableFormator, TableMode, TableOp};
 use adw::prelude::*;
 use adw::subclass::prelude::*;
 use gtk::CompositeTemplate;
 use gtk::gdk;
+use gtk::gio;
 use gtk::glib;
 use gtk::prelude::{TextBufferExt, TextViewExt};
 use sourceview;
@@ -21,6 +22,14 @@ mod imp {
         pub out_view: TemplateChild<sourceview::View>,
         #[template_child]
         pub table_mode: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub row_index: TemplateChild<gtk::SpinButton>,
+        #[template_child]
+        pub column_index: TemplateChild<gtk::SpinButton>,
+        #[template_child]
+        pub sort_mode: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub sort_descending: TemplateChild<gtk::CheckButton>,
 
         pub provider: gtk::CssProvider,
     }
@@ -45,6 +54,20 @@ mod imp {
             klass.install_action("table.execute-copy-result", None, move |obj, _, _| {
                 obj.execute_copy_result();
             });
+
+            klass.install_action_async("table.execute-paste", None, |obj, _, _| async move {
+                obj.execute_paste().await;
+            });
+
+            klass.install_action(
+                "table.edit",
+                Some(glib::VariantTy::STRING),
+                move |obj, _, param| {
+                    if let Some(name) = param.and_then(|p| p.get::<String>()) {
+                        obj.execute_edit(&name);
+                    }
+                },
+            );
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -84,6 +107,31 @@ impl TablePage {
         clipboard.set_text(content.as_str());
     }
 
+    /// 粘贴剪贴板的内容并转换，从网页复制的表格优先使用 text/html
+    async fn execute_paste(&self) {
+        let clipboard = self.clipboard();
+        let html = match clipboard
+            .read_future(&["text/html"], glib::Priority::DEFAULT)
+            .await
+        {
+            Ok((stream, _)) => read_html_stream(stream).await,
+            Err(_) => None,
+        };
+        let content = match html {
+            Some(html) => html,
+            None => match clipboard.read_text_future().await {
+                Ok(Some(text)) => text.to_string(),
+                Ok(None) => return,
+                Err(e) => {
+                    log::error!("error read clipboard: {e}");
+                    return;
+                }
+            },
+        };
+        self.imp().in_view.get().buffer().set_text(&content);
+        self.execute_transform();
+    }
+
     fn execute_transform(&self) {
         let ibuffer: gtk::TextBuffer = self.imp().in_view.get().buffer();
         let content = ibuffer.text(&ibuffer.bounds().0, &ibuffer.bounds().1, false);
@@ -94,6 +142,19 @@ impl TablePage {
                 0 => TableMode::Markdown,
                 1 => TableMode::MarkdownGFM,
                 2 => TableMode::Asciidoc,
+                3 => TableMode::Csv,
+                4 => TableMode::Tsv,
+                5 => TableMode::Html,
+                6 => TableMode::RstGrid,
+                7 => TableMode::RstSimple,
+                8 => TableMode::Org,
+                9 => TableMode::Latex,
+                10 => TableMode::MediaWiki,
+                11 => TableMode::Jira,
+                12 => TableMode::BoxDrawing(BoxBorder::Single),
+                13 => TableMode::BoxDrawing(BoxBorder::Double),
+                14 => TableMode::BoxDrawing(BoxBorder::Rounded),
+                15 => TableMode::BoxDrawing(BoxBorder::Heavy),
                 _ => TableMode::Markdown,
             };
             let cellw = 40;
@@ -107,6 +168,52 @@ impl TablePage {
         }
     }
 
+    /// 编辑输入的表格，保持输入的格式，然后重新转换
+    fn execute_edit(&self, name: &str) {
+        let imp = self.imp();
+        // 界面上的行列从 1 开始
+        let x = (imp.column_index.value_as_int().max(1) - 1) as usize;
+        let y = (imp.row_index.value_as_int().max(1) - 1) as usize;
+        let op = match name {
+            "insert-row" => TableOp::InsertRow(y),
+            "delete-row" => TableOp::DeleteRow(y),
+            "move-row-up" => TableOp::MoveRow(y, y.saturating_sub(1)),
+            "move-row-down" => TableOp::MoveRow(y, y + 1),
+            "insert-column" => TableOp::InsertColumn(x),
+            "delete-column" => TableOp::DeleteColumn(x),
+            "move-column-left" => TableOp::MoveColumn(x, x.saturating_sub(1)),
+            "move-column-right" => TableOp::MoveColumn(x, x + 1),
+            "transpose" => TableOp::Transpose,
+            "sort" => TableOp::Sort {
+                column: x,
+                mode: match imp.sort_mode.get().selected() {
+                    1 => SortMode::Numeric,
+                    2 => SortMode::Natural,
+                    _ => SortMode::Lexical,
+                },
+                descending: imp.sort_descending.get().is_active(),
+            },
+            "dedup" => TableOp::Dedup,
+            _ => return,
+        };
+
+        let ibuffer: gtk::TextBuffer = imp.in_view.get().buffer();
+        let content = ibuffer.text(&ibuffer.bounds().0, &ibuffer.bounds().1, false);
+        let mut formator: TableFormator = TableFormator::new(40, 99);
+        let Some(text) = formator.edit(content.as_str(), &op) else {
+            log::warn!("table edit {:?} is not applicable", op);
+            return;
+        };
+        ibuffer.set_text(&text);
+        // 移动后选择跟随移动的行或列
+        match op {
+            TableOp::MoveRow(_, to) => imp.row_index.set_value((to + 1) as f64),
+            TableOp::MoveColumn(_, to) => imp.column_index.set_value((to + 1) as f64),
+            _ => {}
+        }
+        self.execute_transform();
+    }
+
     fn execute_clear(&self) {
         let ibuffer: gtk::TextBuffer = self.imp().in_view.get().buffer();
         ibuffer.set_text("");
@@ -197,3 +304,26 @@ impl Default for TablePage {
         Self::new()
     }
 }
+
+// 读取剪贴板中的 html，部分浏览器使用带 BOM 的 UTF-16
+async fn read_html_stream(stream: gio::InputStream) -> Option<String> {
+    let mut bytes: Vec<u8> = Vec::new();
+    loop {
+        let chunk = stream
+            .read_bytes_future(8192, glib::Priority::DEFAULT)
+            .await
+            .ok()?;
+        if chunk.is_empty() {
+            break;
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    if let Some(utf16) = bytes.strip_prefix(&[0xff, 0xfe]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        return Some(String::from_utf16_lossy(&units));
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
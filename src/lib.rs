@@ -0,0 +1,6 @@
+pub mod cli;
+pub mod config;
+pub mod core;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod utils;
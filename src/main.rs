@@ -1,23 +1,34 @@
+#[cfg(feature = "gui")]
 mod application;
-mod config;
-mod core;
+#[cfg(feature = "gui")]
 mod gui;
-mod utils;
 
+use asciibox::cli;
+#[cfg(feature = "gui")]
+use asciibox::{config, core, utils};
+#[cfg(feature = "gui")]
 use core::AppSettings;
 
+#[cfg(feature = "gui")]
 use application::BasicApplication;
+#[cfg(feature = "gui")]
 use gettextrs::LocaleCategory;
+#[cfg(feature = "gui")]
 use gtk::prelude::*;
+#[cfg(feature = "gui")]
 use gtk::{gio, glib};
+#[cfg(feature = "gui")]
 use rust_embed::Embed;
 
+#[cfg(feature = "gui")]
 #[derive(Embed)]
 #[folder = "data/bin"]
 struct Asset;
 
+#[cfg(feature = "gui")]
 use config::{APP_ID, APP_NAME, PKGDATA_DIR};
 
+#[cfg(feature = "gui")]
 fn init_resource() -> bool {
     let res_name = format!("{}.gresource", APP_NAME);
     let res_name = &res_name;
@@ -33,6 +44,7 @@ fn init_resource() -> bool {
     return true;
 }
 
+#[cfg(feature = "gui")]
 fn init_i18n() {
     let settings = AppSettings::get();
 
@@ -44,8 +56,16 @@ fn init_i18n() {
     gettextrs::textdomain(config::APP_NAME).expect("Unable to switch to the text domain");
 }
 
+#[cfg(feature = "gui")]
 fn main() -> glib::ExitCode {
     env_logger::init();
+
+    // 带子命令时以命令行方式运行，不初始化 gtk
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if cli::is_command(&args) {
+        return glib::ExitCode::from(cli::run(&args));
+    }
+
     gtk::init().expect("can not init gtk");
 
     init_resource();
@@ -60,8 +80,17 @@ fn main() -> glib::ExitCode {
     app.run()
 }
 
+#[cfg(feature = "gui")]
 fn setup_shortcuts(app: &BasicApplication) {
     app.set_accels_for_action("app.quit", &["<Ctrl>q"]);
     app.set_accels_for_action("app.show-shortcuts", &["<Ctrl>h"]);
     app.set_accels_for_action("win.execute-transform", &["<Ctrl>r"]);
 }
+
+// 没有 GTK 时只提供命令行
+#[cfg(not(feature = "gui"))]
+fn main() -> std::process::ExitCode {
+    env_logger::init();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::ExitCode::from(cli::run(&args))
+}
@@ -0,0 +1,548 @@
+use std::io;
+use std::time::Duration;
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Paragraph, Tabs},
+};
+
+use super::editor::Editor;
+use crate::core::asciibox::ab_graph::{NavKey, SelectionEvent};
+use crate::core::{AsciiBoxMap, BoxBorder, RenderedGraph, TableFormator, TableMode};
+
+/// 终端界面的页面
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Page {
+    #[default]
+    Flowchart,
+    Table,
+}
+
+impl Page {
+    const ALL: [Page; 2] = [Page::Flowchart, Page::Table];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Page::Flowchart => "Flowchart",
+            Page::Table => "Table",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Page::Flowchart => Page::Table,
+            Page::Table => Page::Flowchart,
+        }
+    }
+}
+
+const TABLE_MODES: [TableMode; 16] = [
+    TableMode::Markdown,
+    TableMode::MarkdownGFM,
+    TableMode::Asciidoc,
+    TableMode::Csv,
+    TableMode::Tsv,
+    TableMode::Html,
+    TableMode::RstGrid,
+    TableMode::RstSimple,
+    TableMode::Org,
+    TableMode::Latex,
+    TableMode::MediaWiki,
+    TableMode::Jira,
+    TableMode::BoxDrawing(BoxBorder::Single),
+    TableMode::BoxDrawing(BoxBorder::Double),
+    TableMode::BoxDrawing(BoxBorder::Rounded),
+    TableMode::BoxDrawing(BoxBorder::Heavy),
+];
+
+fn table_mode_name(mode: &TableMode) -> &'static str {
+    match mode {
+        TableMode::Markdown => "Markdown",
+        TableMode::MarkdownGFM => "GFM",
+        TableMode::Asciidoc => "Asciidoc",
+        TableMode::Csv => "CSV",
+        TableMode::Tsv => "TSV",
+        TableMode::Html => "HTML",
+        TableMode::RstGrid => "RST Grid",
+        TableMode::RstSimple => "RST Simple",
+        TableMode::Org => "Org",
+        TableMode::Latex => "LaTeX",
+        TableMode::MediaWiki => "MediaWiki",
+        TableMode::Jira => "Jira",
+        TableMode::BoxDrawing(BoxBorder::Single) => "Box",
+        TableMode::BoxDrawing(BoxBorder::Double) => "Box Double",
+        TableMode::BoxDrawing(BoxBorder::Rounded) => "Box Rounded",
+        TableMode::BoxDrawing(BoxBorder::Heavy) => "Box Heavy",
+        TableMode::BoxDrawing(BoxBorder::Ascii) => "Box ASCII",
+    }
+}
+
+/// 终端界面的状态
+///
+/// 左侧为输入的编辑器，右侧为实时渲染的结果。按键由 [`App::handle_key`]
+/// 处理，绘制由 [`App::draw`] 完成，两者都不依赖真实终端，方便测试。
+pub struct App {
+    page: Page,
+    flowchart: Editor,
+    table: Editor,
+    table_mode: usize,
+    graph: Option<RenderedGraph<String, String>>,
+    // 方向键在流程图中选择节点，而不是移动光标
+    graph_focus: bool,
+    // 状态栏显示的选择信息
+    message: String,
+    // 逐帧显示布局的收敛过程
+    animate: bool,
+    table_output: String,
+    // 输入改变后需要重新渲染
+    dirty: bool,
+    quit: bool,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self {
+            page: Page::default(),
+            flowchart: Editor::default(),
+            table: Editor::default(),
+            table_mode: 0,
+            graph: None,
+            graph_focus: false,
+            message: String::new(),
+            animate: false,
+            table_output: String::new(),
+            dirty: true,
+            quit: false,
+        }
+    }
+}
+
+impl App {
+    pub fn new(flowchart: &str, table: &str) -> Self {
+        Self {
+            flowchart: Editor::new(flowchart),
+            table: Editor::new(table),
+            ..Default::default()
+        }
+    }
+
+    pub fn page(&self) -> Page {
+        self.page
+    }
+
+    pub fn set_page(&mut self, page: Page) {
+        self.page = page;
+    }
+
+    pub fn table_mode(&self) -> &TableMode {
+        &TABLE_MODES[self.table_mode]
+    }
+
+    pub fn graph_focus(&self) -> bool {
+        self.graph_focus
+    }
+
+    /// 流程图中选中的节点
+    pub fn selected_node(&self) -> Option<&str> {
+        let graph = self.graph.as_ref()?;
+        graph.selected().map(|n| graph.graph()[n].as_str())
+    }
+
+    pub fn animate(&self) -> bool {
+        self.animate
+    }
+
+    /// 切换动画模式，关闭时直接完成布局
+    pub fn set_animate(&mut self, animate: bool) {
+        self.animate = animate;
+        if !animate && let Some(graph) = self.graph.as_mut() {
+            graph.run_simulation();
+        }
+    }
+
+    /// 推进一帧动画，返回布局是否仍在变化
+    pub fn tick(&mut self) -> bool {
+        match self.graph.as_mut() {
+            Some(graph) if self.animate => graph.animate(5),
+            _ => false,
+        }
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    fn editor(&mut self) -> &mut Editor {
+        match self.page {
+            Page::Flowchart => &mut self.flowchart,
+            Page::Table => &mut self.table,
+        }
+    }
+
+    /// 运行事件循环，直到用户退出
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            // 动画进行时按帧刷新，否则等待按键
+            if self.tick() && !event::poll(Duration::from_millis(33))? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.handle_key(key);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if self.graph_focus && self.page == Page::Flowchart {
+            self.handle_graph_key(key);
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Char('q') if ctrl => self.quit = true,
+            KeyCode::Tab => self.page = self.page.next(),
+            KeyCode::F(n) if (n as usize) <= Page::ALL.len() && n > 0 => {
+                self.page = Page::ALL[n as usize - 1];
+            }
+            KeyCode::Char('g') if ctrl && self.page == Page::Flowchart => {
+                self.graph_focus = true;
+            }
+            KeyCode::Char('t') if ctrl => {
+                self.table_mode = (self.table_mode + 1) % TABLE_MODES.len();
+                self.dirty = true;
+            }
+            _ => {
+                if self.editor().handle_key(key) {
+                    self.dirty = true;
+                }
+            }
+        }
+    }
+
+    /// 流程图获得焦点时的按键：方向键沿边移动，Tab 依次选择，Enter 激活
+    fn handle_graph_key(&mut self, key: KeyEvent) {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let nav = match key.code {
+            KeyCode::Esc => {
+                self.graph_focus = false;
+                return;
+            }
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.graph_focus = false;
+                return;
+            }
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.quit = true;
+                return;
+            }
+            // Alt+方向键拖动选中的节点
+            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right if alt => {
+                let (dx, dy) = match key.code {
+                    KeyCode::Up => (0, -1),
+                    KeyCode::Down => (0, 1),
+                    KeyCode::Left => (-2, 0),
+                    _ => (2, 0),
+                };
+                if let Some(graph) = self.graph.as_mut()
+                    && let Some(node) = graph.selected()
+                {
+                    graph.drag_node(node, dx, dy);
+                    if !self.animate {
+                        graph.run_simulation();
+                    }
+                }
+                return;
+            }
+            // Shift+方向键平移视图
+            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right if shift => {
+                let (dx, dy) = match key.code {
+                    KeyCode::Up => (0, -1),
+                    KeyCode::Down => (0, 1),
+                    KeyCode::Left => (-4, 0),
+                    _ => (4, 0),
+                };
+                if let Some(graph) = self.graph.as_mut() {
+                    graph.pan(dx, dy);
+                }
+                return;
+            }
+            KeyCode::Char('c') => {
+                if let Some(graph) = self.graph.as_mut()
+                    && let Some(node) = graph.selected()
+                {
+                    graph.center_on_node(node);
+                }
+                return;
+            }
+            KeyCode::Char('a') => {
+                self.set_animate(!self.animate);
+                return;
+            }
+            KeyCode::Char('p') => {
+                if let Some(graph) = self.graph.as_mut()
+                    && let Some(node) = graph.selected()
+                {
+                    let pinned = graph.is_node_pinned(node);
+                    graph.set_node_pinned(node, !pinned);
+                    if !self.animate {
+                        graph.run_simulation();
+                    }
+                }
+                return;
+            }
+            KeyCode::Char('m') => {
+                if let Some(graph) = self.graph.as_mut() {
+                    let show = !graph.viewport().minimap;
+                    graph.set_minimap(show);
+                }
+                return;
+            }
+            KeyCode::Up => NavKey::Up,
+            KeyCode::Down => NavKey::Down,
+            KeyCode::Left => NavKey::Left,
+            KeyCode::Right => NavKey::Right,
+            KeyCode::Tab => NavKey::Next,
+            KeyCode::BackTab => NavKey::Prev,
+            KeyCode::Enter => {
+                if let Some(graph) = self.graph.as_mut() {
+                    graph.activate_selection();
+                }
+                self.poll_selection();
+                return;
+            }
+            _ => return,
+        };
+        self.refresh();
+        if let Some(graph) = self.graph.as_mut()
+            && graph.move_selection(nav)
+            && let Some(node) = graph.selected()
+        {
+            graph.reveal_node(node);
+        }
+        self.poll_selection();
+    }
+
+    // 把选择事件转为状态栏信息
+    fn poll_selection(&mut self) {
+        let Some(graph) = self.graph.as_mut() else {
+            return;
+        };
+        for event in graph.take_selection_events() {
+            self.message = match event {
+                SelectionEvent::Selected(n) => format!("Selected: {}", graph.graph()[n]),
+                SelectionEvent::Activated(n) => format!("Activated: {}", graph.graph()[n]),
+                SelectionEvent::Cleared => String::new(),
+            };
+        }
+    }
+
+    /// 根据输入重新生成流程图和表格
+    fn refresh(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+
+        let content = self.flowchart.text();
+        self.graph = if content.trim().is_empty() {
+            None
+        } else {
+            let mut graph = AsciiBoxMap::load_content(&content).to_rendered();
+            if !self.animate {
+                graph.run_simulation();
+            }
+            // 重新生成后保留之前的选择和视图
+            if let Some(old) = self.graph.as_ref() {
+                graph.select(old.selected());
+                graph.take_selection_events();
+                *graph.viewport_mut() = *old.viewport();
+            }
+            Some(graph)
+        };
+
+        let content = self.table.text();
+        self.table_output = if content.trim().is_empty() {
+            String::new()
+        } else {
+            TableFormator::new(40, 99).do_format(&content, self.table_mode())
+        };
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        self.refresh();
+
+        let [tabs_area, body, status] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [input, output] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(body);
+
+        let selected = Page::ALL.iter().position(|p| *p == self.page).unwrap_or(0);
+        let tabs = Tabs::new(Page::ALL.iter().map(|p| p.title()))
+            .select(selected)
+            .highlight_style(Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED));
+        frame.render_widget(tabs, tabs_area);
+
+        let block = Block::bordered().title(" Input ");
+        match self.page {
+            Page::Flowchart => {
+                self.flowchart
+                    .render(frame, input, block, !self.graph_focus);
+                let mut block = Block::bordered().title(" Graph ");
+                if self.graph_focus {
+                    block = block.border_style(Style::new().fg(Color::Yellow));
+                }
+                let inner = block.inner(output);
+                frame.render_widget(block, output);
+                if let Some(graph) = self.graph.as_mut() {
+                    frame.render_widget(graph.widget().settle_indicator(self.animate), inner);
+                }
+            }
+            Page::Table => {
+                self.table.render(frame, input, block, true);
+                let title = format!(" {} ", table_mode_name(self.table_mode()));
+                let result = Paragraph::new(self.table_output.as_str())
+                    .block(Block::bordered().title(title));
+                frame.render_widget(result, output);
+            }
+        }
+
+        let help = match self.page {
+            Page::Flowchart if self.graph_focus => {
+                "Arrows/Tab select  Shift/Alt+Arrows pan/drag  A animate  P pin  C center  M minimap  Esc back"
+            }
+            Page::Flowchart => "Tab/F1/F2 switch page  Ctrl-G select nodes  Esc quit",
+            Page::Table => "Tab/F1/F2 switch page  Ctrl-T table mode  Esc quit",
+        };
+        let help = if self.page == Page::Flowchart && !self.message.is_empty() {
+            format!("{}  |  {}", self.message, help)
+        } else {
+            help.to_string()
+        };
+        frame.render_widget(Line::styled(help, Style::new().fg(Color::DarkGray)), status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn screen(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let mut text = String::new();
+        for y in 0..buffer.area.height {
+            for x in 0..buffer.area.width {
+                text.push_str(buffer[(x, y)].symbol());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_flowchart_page() {
+        let mut app = App::new("a[Start]-->b[End]", "");
+        let text = screen(&mut app);
+        assert!(text.contains("Flowchart"));
+        assert!(text.contains("Start"));
+        assert!(text.contains("End"));
+
+        for c in "\nb-->c[Next]".chars() {
+            press(
+                &mut app,
+                if c == '\n' {
+                    KeyCode::Enter
+                } else {
+                    KeyCode::Char(c)
+                },
+            );
+        }
+        assert!(screen(&mut app).contains("Next"));
+    }
+
+    #[test]
+    fn test_table_page() {
+        let mut app = App::new("", "a,b\n1,2");
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.page(), Page::Table);
+        let text = screen(&mut app);
+        assert!(text.contains("Markdown"));
+        assert!(text.contains("| a"), "{}", text);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+        assert_eq!(app.table_mode(), &TableMode::Asciidoc);
+        assert!(screen(&mut app).contains("|==="));
+
+        press(&mut app, KeyCode::F(1));
+        assert_eq!(app.page(), Page::Flowchart);
+        press(&mut app, KeyCode::Esc);
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn test_graph_selection() {
+        let mut app = App::new("a[Start]-->b[End]", "");
+        screen(&mut app);
+        app.handle_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+        assert!(app.graph_focus());
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.selected_node(), Some("Start"));
+        assert_eq!(app.page(), Page::Flowchart);
+        let text = screen(&mut app);
+        assert!(text.contains("║ Start ║"), "{}", text);
+        assert!(text.contains("Selected: Start"), "{}", text);
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Enter);
+        assert!(screen(&mut app).contains("Activated: End"));
+
+        // 编辑后保留选择
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.graph_focus() && !app.should_quit());
+        press(&mut app, KeyCode::Enter);
+        screen(&mut app);
+        assert_eq!(app.selected_node(), Some("End"));
+    }
+
+    #[test]
+    fn test_graph_animation() {
+        let mut app = App::new("a[Start]-->b[End]", "");
+        app.set_animate(true);
+        let text = screen(&mut app);
+        assert!(text.contains("settling 0"), "{}", text);
+        while app.tick() {}
+        assert!(screen(&mut app).contains("settled"));
+
+        // 拖动节点后重新收敛
+        app.handle_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+        press(&mut app, KeyCode::Tab);
+        app.handle_key(KeyEvent::new(KeyCode::Right, KeyModifiers::ALT));
+        assert!(app.tick());
+        press(&mut app, KeyCode::Char('a'));
+        assert!(!app.animate() && !app.tick());
+        assert!(screen(&mut app).contains("Start"));
+    }
+}
@@ -0,0 +1,231 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Position, Rect},
+    widgets::{Block, Paragraph},
+};
+
+use crate::utils::cn_length;
+
+/// 简单的多行文本编辑器，光标按字符计数
+#[derive(Debug, Clone)]
+pub struct Editor {
+    lines: Vec<String>,
+    row: usize,
+    col: usize,
+    // 第一行可见行
+    scroll: usize,
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+            scroll: 0,
+        }
+    }
+}
+
+impl Editor {
+    pub fn new(text: &str) -> Self {
+        let mut editor = Self::default();
+        editor.set_text(text);
+        editor
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// 替换全部内容，光标移动到末尾
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = text.split('\n').map(|s| s.to_string()).collect();
+        self.row = self.lines.len() - 1;
+        self.col = self.line_len(self.row);
+        self.scroll = 0;
+    }
+
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    // 字符位置对应的字节位置
+    fn byte_index(&self) -> usize {
+        self.lines[self.row]
+            .char_indices()
+            .nth(self.col)
+            .map(|(i, _)| i)
+            .unwrap_or(self.lines[self.row].len())
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        let i = self.byte_index();
+        self.lines[self.row].insert(i, c);
+        self.col += 1;
+    }
+
+    pub fn insert_newline(&mut self) {
+        let i = self.byte_index();
+        let rest = self.lines[self.row].split_off(i);
+        self.row += 1;
+        self.lines.insert(self.row, rest);
+        self.col = 0;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let i = self.byte_index();
+            self.lines[self.row].remove(i);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.col < self.line_len(self.row) {
+            let i = self.byte_index();
+            self.lines[self.row].remove(i);
+        } else if self.row + 1 < self.lines.len() {
+            let line = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.col < self.line_len(self.row) {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.row > 0 {
+            self.row -= 1;
+            self.col = self.col.min(self.line_len(self.row));
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = self.col.min(self.line_len(self.row));
+        }
+    }
+
+    /// 处理编辑按键，返回内容是否改变
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(c) => self.insert_char(c),
+            KeyCode::Enter => self.insert_newline(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => {
+                self.move_left();
+                return false;
+            }
+            KeyCode::Right => {
+                self.move_right();
+                return false;
+            }
+            KeyCode::Up => {
+                self.move_up();
+                return false;
+            }
+            KeyCode::Down => {
+                self.move_down();
+                return false;
+            }
+            KeyCode::Home => {
+                self.col = 0;
+                return false;
+            }
+            KeyCode::End => {
+                self.col = self.line_len(self.row);
+                return false;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// 绘制编辑器，focused 时显示光标
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, block: Block, focused: bool) {
+        let inner = block.inner(area);
+        let height = inner.height as usize;
+        // 保证光标所在行可见
+        if self.row < self.scroll {
+            self.scroll = self.row;
+        } else if height > 0 && self.row >= self.scroll + height {
+            self.scroll = self.row + 1 - height;
+        }
+
+        let text = self.lines[self.scroll..].join("\n");
+        frame.render_widget(Paragraph::new(text).block(block), area);
+
+        if focused {
+            let prefix: String = self.lines[self.row].chars().take(self.col).collect();
+            let x = inner.x + cn_length(&prefix) as u16;
+            let y = inner.y + (self.row - self.scroll) as u16;
+            if x < inner.right() && y < inner.bottom() {
+                frame.set_cursor_position(Position::new(x, y));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit() {
+        let mut editor = Editor::new("a-->b");
+        editor.insert_newline();
+        for c in "b-->你".chars() {
+            editor.insert_char(c);
+        }
+        assert_eq!(editor.text(), "a-->b\nb-->你");
+        assert_eq!(editor.cursor(), (1, 5));
+
+        editor.backspace();
+        editor.insert_char('c');
+        editor.move_up();
+        editor.backspace();
+        assert_eq!(editor.text(), "a-->\nb-->c");
+
+        editor.move_right();
+        editor.move_right();
+        editor.backspace();
+        editor.backspace();
+        assert_eq!(editor.text(), "a-->-->c");
+        editor.delete();
+        assert_eq!(editor.text(), "a-->->c");
+    }
+}
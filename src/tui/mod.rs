@@ -0,0 +1,11 @@
+//! 终端界面，在没有 GTK 的环境（比如 SSH）中使用
+mod app;
+mod editor;
+
+pub use app::{App, Page};
+pub use editor::Editor;
+
+/// 启动终端界面，直到用户退出
+pub fn run() -> std::io::Result<()> {
+    ratatui::run(|terminal| App::default().run(terminal))
+}
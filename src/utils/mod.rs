@@ -1,11 +1,15 @@
 mod calurator;
 mod checker;
 mod files;
+#[cfg(feature = "gui")]
 mod g_dialog;
+#[cfg(feature = "gui")]
 mod g_resource;
 
 pub use calurator::cn_length;
 pub use checker::check_is_color;
 pub use files::{list_files_in_dir, read_text, save_file};
+#[cfg(feature = "gui")]
 pub use g_dialog::save_dialog;
+#[cfg(feature = "gui")]
 pub use g_resource::load_gresource;